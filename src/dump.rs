@@ -0,0 +1,164 @@
+//! `dump` is a versioned JSON copy of all the data, for moving the app between machines. Users
+//! aren't included as the basic auth user is set on the command line rather than stored.
+
+use crate::{Error, Game, Player, Settings};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// `DUMP_VERSION` is incremented when a change to the dump format can't be read by older versions.
+pub const DUMP_VERSION: u32 = 1;
+
+/// `Dump` holds every player, game (state and data) and the settings.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Dump {
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub settings: Settings,
+    pub players: Vec<Player>,
+    pub games: Vec<Game>,
+}
+
+/// `ImportMode` is how a `Dump` is loaded alongside the existing data.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "kebab-case")]
+pub enum ImportMode {
+    /// delete the existing players and games, and use the dump's settings.
+    Replace,
+    /// add the games and the players not matched by name, with new ids, keeping the existing
+    /// settings.
+    #[default]
+    Merge,
+}
+
+/// `ImportSummary` counts what an import loaded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ImportSummary {
+    pub players: usize,
+    pub games: usize,
+    /// players merged into an existing player with the same name.
+    pub matched: usize,
+}
+
+impl std::fmt::Display for ImportSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "imported {} players and {} games, matched {} existing players",
+            self.players, self.games, self.matched
+        )
+    }
+}
+
+/// `Merge` is a `Dump` ready to add to the existing data, with new ids.
+pub struct Merge {
+    pub players: Vec<Player>,
+    pub games: Vec<Game>,
+    pub summary: ImportSummary,
+}
+
+impl Dump {
+    /// `parse` reads and validates a JSON dump.
+    pub fn parse(json: &str) -> Result<Dump, Error> {
+        // check the version first, so a newer dump gets a helpful error rather than a parse error.
+        let version = serde_json::from_str::<serde_json::Value>(json)
+            .map_err(|e| Error::InvalidInput(format!("dump is not JSON: {e}")))?
+            .get("version")
+            .and_then(serde_json::Value::as_u64);
+        if version != Some(DUMP_VERSION.into()) {
+            return Err(Error::InvalidInput(format!(
+                "unsupported dump version {}, expected {}",
+                version.map_or("none".to_string(), |v| v.to_string()),
+                DUMP_VERSION
+            )));
+        }
+
+        let dump: Dump = serde_json::from_str(json)
+            .map_err(|e| Error::InvalidInput(format!("invalid dump: {e}")))?;
+
+        let mut player_ids: Vec<u32> = dump.players.iter().map(|p| p.id).collect();
+        player_ids.sort_unstable();
+        player_ids.dedup();
+        if player_ids.len() != dump.players.len() {
+            return Err(Error::InvalidInput(
+                "invalid dump: duplicate player ids".to_string(),
+            ));
+        }
+
+        let mut game_ids: Vec<u32> = dump.games.iter().map(|g| g.id).collect();
+        game_ids.sort_unstable();
+        game_ids.dedup();
+        if game_ids.len() != dump.games.len() {
+            return Err(Error::InvalidInput(
+                "invalid dump: duplicate game ids".to_string(),
+            ));
+        }
+
+        Ok(dump)
+    }
+
+    /// `merge` prepares the dump to add to the `existing` players. Players are matched to existing
+    /// players by name, like a roster import, and the rest and every game get new ids counting
+    /// from `next_player_id` and `next_game_id`. The same id on two machines is usually a
+    /// different player or game, so ids are never matched.
+    pub fn merge(self, existing: &[Player], next_player_id: u32, next_game_id: u32) -> Merge {
+        let mut ids: HashMap<u32, u32> = HashMap::new();
+        let mut next_player_id = next_player_id;
+        let mut new_id = |ids: &mut HashMap<u32, u32>, id: u32| {
+            _ = ids.insert(id, next_player_id);
+            next_player_id += 1;
+        };
+
+        let mut players = vec![];
+        let mut matched = 0;
+        for player in self.players {
+            let name = player.name.trim().to_lowercase();
+            match existing
+                .iter()
+                .find(|e| e.name.trim().to_lowercase() == name)
+            {
+                Some(e) => {
+                    _ = ids.insert(player.id, e.id);
+                    matched += 1;
+                }
+                None => {
+                    new_id(&mut ids, player.id);
+                    players.push(Player {
+                        id: ids[&player.id],
+                        ..player
+                    });
+                }
+            }
+        }
+        // players since deleted are only in the games, they get new ids too so they aren't
+        // mistaken for an existing player.
+        for game in &self.games {
+            for player in &game.shared.players {
+                if !ids.contains_key(&player.id) {
+                    new_id(&mut ids, player.id);
+                }
+            }
+        }
+
+        let mut games = self.games;
+        games.sort_by_key(|g| g.id); // keep the games in the order they were played.
+        for (game, id) in games.iter_mut().zip(next_game_id..) {
+            game.id = id;
+            game.shared.remap_players(&ids);
+        }
+
+        Merge {
+            summary: ImportSummary {
+                players: players.len(),
+                games: games.len(),
+                matched,
+            },
+            players,
+            games,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::Internal(e.to_string()))
+    }
+}
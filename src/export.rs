@@ -0,0 +1,214 @@
+//! `export` writes players and games as CSV for spreadsheets, durations in decimal minutes.
+
+use crate::{Game, GameView, Player};
+use chrono::{DateTime, SecondsFormat, TimeDelta, Utc};
+
+// field quotes a CSV field when it contains a separator, quote or line break.
+fn field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn row(fields: &[String]) -> String {
+    let fields: Vec<String> = fields.iter().map(|f| field(f)).collect();
+    format!("{}\r\n", fields.join(","))
+}
+
+fn minutes(delta: TimeDelta) -> String {
+    format!("{:.2}", delta.num_milliseconds() as f64 / 60_000.0)
+}
+
+fn time(time: Option<DateTime<Utc>>) -> String {
+    time.map(|t| t.to_rfc3339_opts(SecondsFormat::Secs, true))
+        .unwrap_or_default()
+}
+
+/// `players_csv` lists each player with their totals from finished games.
+pub fn players_csv(players: &[Player]) -> String {
+    let mut csv = row(&[
+        "id".to_string(),
+        "number".to_string(),
+        "name".to_string(),
+        "position".to_string(),
+        "attributes".to_string(),
+        "play_count".to_string(),
+        "played_minutes".to_string(),
+    ]);
+    for player in players {
+        csv.push_str(&row(&[
+            player.id.to_string(),
+            player.number.to_string(),
+            player.name.clone(),
+            player.position.clone(),
+            player
+                .attributes
+                .iter()
+                .map(|a| a.to_string())
+                .collect::<Vec<String>>()
+                .join(";"),
+            player.play_count.to_string(),
+            minutes(player.play_duration),
+        ]));
+    }
+
+    csv
+}
+
+/// `games_csv` lists each game with its result, oldest first.
+pub fn games_csv(games: &[Game]) -> String {
+    let mut csv = row(&[
+        "id".to_string(),
+        "season".to_string(),
+        "round".to_string(),
+        "scheduled_at".to_string(),
+        "opponent".to_string(),
+        "venue".to_string(),
+        "state".to_string(),
+        "start_time".to_string(),
+        "end_time".to_string(),
+        "periods".to_string(),
+        "score_us".to_string(),
+        "score_them".to_string(),
+        "mvp".to_string(),
+    ]);
+
+    let mut games: Vec<&Game> = games.iter().collect();
+    games.sort_by_key(|g| g.id);
+    for game in games {
+        let view = GameView::from(game);
+        csv.push_str(&row(&[
+            view.id.to_string(),
+            view.season.clone(),
+            view.round.clone(),
+            time(view.scheduled_at),
+            view.opponent.clone(),
+            view.venue.clone(),
+            view.state.to_string(),
+            time(view.start_time),
+            time(view.end_time),
+            view.periods.len().to_string(),
+            view.score_us.to_string(),
+            view.score_them.to_string(),
+            view.mvp.map(|id| view.player_name(&id)).unwrap_or_default(),
+        ]));
+    }
+
+    csv
+}
+
+/// `game_minutes_csv` lists each player's minutes in each period of the game, with their totals
+/// and whether they met the minimum participation.
+pub fn game_minutes_csv(game: &Game) -> String {
+    let mut header = vec![
+        "game_id".to_string(),
+        "player_id".to_string(),
+        "number".to_string(),
+        "name".to_string(),
+        "attended".to_string(),
+        "play_count".to_string(),
+    ];
+    for i in 1..=game.shared.periods.len() {
+        header.push(format!("period_{}_minutes", i));
+    }
+    header.extend([
+        "played_minutes".to_string(),
+        "points".to_string(),
+        "min_met".to_string(),
+    ]);
+    let mut csv = row(&header);
+
+    for participation in game.participation() {
+        let play_count = game
+            .shared
+            .players
+            .iter()
+            .find(|p| p.id == participation.player_id)
+            .map_or(0, |p| p.play_count);
+        let mut fields = vec![
+            game.id.to_string(),
+            participation.player_id.to_string(),
+            participation.number.to_string(),
+            participation.name.clone(),
+            participation.attended.to_string(),
+            play_count.to_string(),
+        ];
+        fields.extend(participation.period_played.iter().map(|p| minutes(*p)));
+        fields.extend([
+            minutes(participation.played),
+            game.player_points(&participation.player_id).to_string(),
+            participation
+                .compliant
+                .map(|c| c.to_string())
+                .unwrap_or_default(),
+        ]);
+        csv.push_str(&row(&fields));
+    }
+
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Event, Team};
+    use crate::{Attribute, Settings};
+
+    #[test]
+    fn test_field_quoting() {
+        assert_eq!(field("Jo"), "Jo");
+        assert_eq!(field("Smith, Jo"), "\"Smith, Jo\"");
+        assert_eq!(field("Jo \"JJ\" Lee"), "\"Jo \"\"JJ\"\" Lee\"");
+        assert_eq!(field("two\nlines"), "\"two\nlines\"");
+        assert_eq!(field("a\rb"), "\"a\rb\"");
+        assert_eq!(row(&["a,b".to_string(), String::new()]), "\"a,b\",\r\n");
+    }
+
+    #[test]
+    fn test_players_csv() {
+        let player = Player {
+            position: "guard, wing".to_string(),
+            attributes: vec![Attribute::Tall, Attribute::Beginner],
+            play_count: 2,
+            play_duration: TimeDelta::seconds(90),
+            ..Player::new(3, 7, "Jo \"JJ\" Lee".to_string())
+        };
+        let csv = players_csv(&[player]);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+
+        assert_eq!(
+            lines[1],
+            "3,7,\"Jo \"\"JJ\"\" Lee\",\"guard, wing\",tall;beginner,2,1.50"
+        );
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_game_minutes_csv() {
+        let players = vec![
+            Player::new(5, 10, "Sam".to_string()),
+            Player::new(2, 4, "Smith, Jo".to_string()),
+        ];
+        let mut game = Game::new(1, players, Settings::default())
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"));
+        game.shared.players[1].sub_on();
+        assert!(game.record_score(Team::Us, Some(2), 3));
+        game = game
+            .on_event(Event::EndGame)
+            .unwrap_or_else(|_| panic!("game should end"));
+
+        let csv = game_minutes_csv(&game);
+        let lines: Vec<&str> = csv.split("\r\n").collect();
+        assert_eq!(
+            lines[0],
+            "game_id,player_id,number,name,attended,play_count,period_1_minutes,played_minutes,points,min_met"
+        );
+        // each row's play count and points are the row's player's.
+        assert_eq!(lines[1], "1,5,10,Sam,true,0,0.00,0.00,0,");
+        assert!(lines[2].starts_with("1,2,4,\"Smith, Jo\",true,1,"));
+        assert!(lines[2].ends_with(",3,"));
+    }
+}
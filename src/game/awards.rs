@@ -0,0 +1,24 @@
+use super::core::Game;
+use super::data::Award;
+
+impl Game {
+    /// `set_award` sets the winner of the award `category`, `None` clearing it.
+    pub fn set_award(&mut self, category: &str, player_id: Option<u32>) {
+        self.shared.awards.retain(|a| a.category != category);
+
+        if let Some(player_id) = player_id {
+            self.shared.awards.push(Award {
+                category: category.to_string(),
+                player_id,
+            });
+        }
+    }
+
+    pub fn award_winner(&self, category: &str) -> Option<u32> {
+        self.shared
+            .awards
+            .iter()
+            .find(|a| a.category == category)
+            .map(|a| a.player_id)
+    }
+}
@@ -0,0 +1,152 @@
+use super::core::Game;
+use super::data::{Card, CardKind};
+use super::state::GameState;
+use chrono::{TimeDelta, Utc};
+
+impl Game {
+    /// `record_card` shows the player a card, sending them off the court. Returns `false` when the
+    /// game hasn't started or has finished.
+    pub fn record_card(&mut self, player_id: &u32, kind: CardKind) -> bool {
+        if !matches!(self.state.kind(), GameState::InProgress | GameState::Paused) {
+            return false;
+        }
+
+        self.shared.cards.push(Card {
+            player_id: *player_id,
+            kind,
+            period: self.shared.periods.len(),
+            time: Utc::now(),
+        });
+
+        if let Some(p) = self.shared.players.iter_mut().find(|p| &p.id == player_id) {
+            p.sub_off();
+        }
+
+        true
+    }
+
+    /// `remove_card` removes the player's most recent card, correcting a mis-tap.
+    pub fn remove_card(&mut self, player_id: &u32) -> bool {
+        self.shared
+            .cards
+            .iter()
+            .rposition(|c| &c.player_id == player_id)
+            .map(|i| self.shared.cards.remove(i))
+            .is_some()
+    }
+
+    /// `suspension_remaining` is the sin bin time left for a card, counting only time while
+    /// periods are played. Red cards last the rest of the game so have no countdown.
+    pub fn suspension_remaining(&self, card: &Card) -> Option<TimeDelta> {
+        let minutes = match card.kind {
+            CardKind::Green => self.shared.settings.green_card_minutes,
+            CardKind::Yellow => self.shared.settings.yellow_card_minutes,
+            CardKind::Red => return None,
+        };
+
+        let served: TimeDelta = self
+            .shared
+            .periods
+            .iter()
+            .map(|p| p.overlap(Some(card.time), None))
+            .sum();
+        let remaining = TimeDelta::minutes(minutes.into()) - served;
+
+        (remaining > TimeDelta::zero()).then_some(remaining)
+    }
+
+    pub fn sent_off(&self, player_id: &u32) -> bool {
+        self.shared
+            .cards
+            .iter()
+            .any(|c| &c.player_id == player_id && c.kind == CardKind::Red)
+    }
+
+    /// `suspended` returns the longest sin bin time left for the player, if any.
+    pub fn suspended(&self, player_id: &u32) -> Option<TimeDelta> {
+        self.shared
+            .cards
+            .iter()
+            .filter(|c| &c.player_id == player_id)
+            .filter_map(|c| self.suspension_remaining(c))
+            .max()
+    }
+
+    /// `active_suspensions` counts the players sent off or in the sin bin, each reducing the
+    /// players allowed on court by one however many cards they've been shown.
+    pub fn active_suspensions(&self) -> u32 {
+        let mut player_ids: Vec<u32> = self
+            .shared
+            .cards
+            .iter()
+            .filter(|c| c.kind == CardKind::Red || self.suspension_remaining(c).is_some())
+            .map(|c| c.player_id)
+            .collect();
+        player_ids.sort();
+        player_ids.dedup();
+
+        player_ids.len() as u32
+    }
+
+    /// `allowed_on_court` is the on court limit less any active suspensions, `None` when there
+    /// is no limit.
+    pub fn allowed_on_court(&self) -> Option<u32> {
+        let limit = self.shared.settings.on_court_limit;
+        (limit > 0).then(|| limit.saturating_sub(self.active_suspensions()))
+    }
+
+    pub fn on_court(&self) -> u32 {
+        self.shared
+            .players
+            .iter()
+            .filter(|p| p.is_playing())
+            .count() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::{Player, Settings};
+
+    fn in_progress() -> Game {
+        let players = (1..=3)
+            .map(|id| Player::new(id, id, id.to_string()))
+            .collect();
+        let settings = Settings {
+            on_court_limit: 5,
+            ..Settings::default()
+        };
+        Game::new(1, players, settings)
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"))
+    }
+
+    #[test]
+    fn test_active_suspensions_counts_players() {
+        let mut game = in_progress();
+        assert_eq!(game.allowed_on_court(), Some(5));
+
+        assert!(game.record_card(&1, CardKind::Yellow));
+        assert!(game.record_card(&1, CardKind::Yellow));
+        assert_eq!(game.active_suspensions(), 1);
+
+        assert!(game.record_card(&1, CardKind::Red));
+        assert_eq!(game.active_suspensions(), 1);
+
+        assert!(game.record_card(&2, CardKind::Green));
+        assert_eq!(game.active_suspensions(), 2);
+        assert_eq!(game.allowed_on_court(), Some(3));
+    }
+
+    #[test]
+    fn test_served_suspension_not_active() {
+        let mut game = in_progress();
+        game.shared.settings.green_card_minutes = 0;
+
+        assert!(game.record_card(&1, CardKind::Green));
+        assert_eq!(game.active_suspensions(), 0);
+        assert_eq!(game.allowed_on_court(), Some(5));
+    }
+}
@@ -2,6 +2,7 @@ use super::data::Data;
 use super::event::{Event, EventError, EventHandler};
 use super::state::{GamePhase, State};
 use crate::player::Player;
+use crate::settings::Settings;
 use serde::{Deserialize, Serialize};
 
 /// `Game` represents a sports game, complete with data like periods, game phase, etc.
@@ -13,17 +14,67 @@ pub struct Game {
 }
 
 impl Game {
-    pub fn new(id: u32, players: Vec<Player>) -> Self {
+    pub fn new(id: u32, players: Vec<Player>, settings: Settings) -> Self {
         Self {
             id,
             shared: Data {
                 periods: vec![],
                 players,
                 mvp: None,
+                settings,
+                lineup_warnings: vec![],
+                fouls: vec![],
+                cards: vec![],
+                substitutions: vec![],
+                scores: vec![],
+                shots: vec![],
+                awards: vec![],
+                voting: None,
+                incidents: vec![],
+                notes: vec![],
+                opponent: String::new(),
+                scheduled_at: None,
+                venue: String::new(),
+                round: String::new(),
+                revision: 0,
+                updated_at: None,
             },
             state: State::NotStarted(GamePhase::default()),
         }
     }
+
+    /// `refresh_roster` replaces the players with the current `roster` before the game starts, eg:
+    /// a fixture entered weeks ago. Players already in the game keep their availability.
+    pub fn refresh_roster(&mut self, roster: &[Player]) {
+        let players = roster
+            .iter()
+            .map(|p| {
+                let mut player = p.reset_stats();
+                if let Some(existing) = self.shared.players.iter().find(|e| e.id == p.id) {
+                    player.late = existing.late;
+                    player.arrived_at = existing.arrived_at;
+                    player.departed_at = existing.departed_at;
+                }
+                player
+            })
+            .collect();
+        self.shared.players = players;
+    }
+
+    /// `check_lineup` re-evaluates the team lineup rules against the players currently on court.
+    pub fn check_lineup(&mut self) {
+        self.shared.lineup_warnings = self.shared.settings.lineup_warnings(&self.shared.players);
+    }
+
+    /// `current_period` returns the 1 based number of the period being played, if any.
+    pub fn current_period(&self) -> Option<usize> {
+        self.shared
+            .periods
+            .last()
+            .filter(|p| p.end_time.is_none())
+            .map(|_| self.shared.periods.len())
+    }
+
     /// `on_event` processes the incoming event for the game.
     /// # Errors
     ///
@@ -35,6 +86,10 @@ impl Game {
             State::InProgress(phase) => phase.on_event(event, self.shared),
             State::Paused(phase) => phase.on_event(event, self.shared),
             State::Finished(phase) => phase.on_event(event, self.shared),
+            State::Cancelled(phase) => phase.on_event(event, self.shared),
+            State::Postponed(phase) => phase.on_event(event, self.shared),
+            State::Forfeited(phase) => phase.on_event(event, self.shared),
+            State::Abandoned(phase) => phase.on_event(event, self.shared),
             // _ => Err(EventError::Invalid), // unnecessary
         }
         .map_or_else(Err, |v| {
@@ -1,6 +1,8 @@
 use crate::player::Player;
-use chrono::{DateTime, Utc};
+use crate::settings::Settings;
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// `Period` represents time sections of a `Game`. In football/soccer 'half' might be
 /// the official term but here we use `Period` for all.
@@ -10,12 +12,228 @@ pub struct Period {
     pub end_time: Option<DateTime<Utc>>, // TODO: time.Time{} equivalent?
 }
 
+/// `Foul` is a personal foul committed by a player, counting towards the team fouls for the period.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Foul {
+    pub player_id: u32,
+    pub period: usize, // 1 based, matching the period count shown.
+    pub time: DateTime<Utc>,
+}
+
+/// `CardKind` is the colour of card shown, green and yellow being a temporary suspension.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CardKind {
+    Green,
+    Yellow,
+    Red,
+}
+
+impl CardKind {
+    pub const ALL: [CardKind; 3] = [CardKind::Green, CardKind::Yellow, CardKind::Red];
+}
+
+impl std::fmt::Display for CardKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Green => write!(f, "green"),
+            Self::Yellow => write!(f, "yellow"),
+            Self::Red => write!(f, "red"),
+        }
+    }
+}
+
+/// `Card` is a card shown to a player.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Card {
+    pub player_id: u32,
+    pub kind: CardKind,
+    pub period: usize, // 1 based, matching the period count shown.
+    pub time: DateTime<Utc>,
+}
+
+/// `Team` is who scored, `Us` being the team using subbers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Team {
+    Us,
+    Them,
+}
+
+/// `Score` is points scored during a period, `player_id` being the scorer for our team.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Score {
+    pub team: Team,
+    pub player_id: Option<u32>,
+    pub points: u32,
+    pub period: usize, // 1 based, matching the period count shown.
+    pub time: DateTime<Utc>,
+}
+
+/// `Shot` is a shot at goal, `x` and `y` locating it on the game's `Surface` diagram with the goal
+/// at the top.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Shot {
+    pub player_id: u32,
+    pub x: u32, // 0 to `Surface::width`.
+    pub y: u32, // 0 to `Surface::length`, 0 being the goal end.
+    pub made: bool,
+    pub period: usize, // 1 based, matching the period count shown.
+    pub time: DateTime<Utc>,
+}
+
+/// `Award` is the winner of one of the team's award categories for a game.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Award {
+    pub category: String,
+    pub player_id: u32,
+}
+
+/// `Incident` is an injury or other incident involving a player, eg: for insurance paperwork.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Incident {
+    pub player_id: u32,
+    pub period: usize, // 1 based, matching the period count shown.
+    pub time: DateTime<Utc>,
+    pub description: String,
+}
+
+/// `Note` is a coach's note about the game, or a player in the game when `player_id` is set.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Note {
+    pub player_id: Option<u32>,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub period: usize, // 1 based, 0 before the game starts.
+    pub time: DateTime<Utc>,
+}
+
+/// `Voting` is player of the match voting by parents and spectators, shared via `token` so voters
+/// don't need to log in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Voting {
+    pub token: String,
+    pub open: bool,
+    pub votes: Vec<Vote>,
+}
+
+/// `Vote` is a device's vote, each device having one vote that can be changed while voting is open.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Vote {
+    pub device_id: String,
+    pub player_id: u32,
+    pub time: DateTime<Utc>,
+}
+
+/// `Substitution` is a player subbed on during a period to replace another, as opposed to choosing
+/// the lineup at the start of a period. Substitutions close together share a `window`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Substitution {
+    pub player_id: u32,
+    pub period: usize, // 1 based, matching the period count shown.
+    pub time: DateTime<Utc>,
+    pub window: u32, // 1 based.
+}
+
 /// `Data` represents the unique data per game.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Data {
     pub periods: Vec<Period>,
     pub players: Vec<Player>,
     pub mvp: Option<u32>, // player_id
+    #[serde(default)]
+    pub settings: Settings,
+    #[serde(default)]
+    pub lineup_warnings: Vec<String>,
+    #[serde(default)]
+    pub fouls: Vec<Foul>,
+    #[serde(default)]
+    pub cards: Vec<Card>,
+    #[serde(default)]
+    pub substitutions: Vec<Substitution>,
+    #[serde(default)]
+    pub scores: Vec<Score>,
+    #[serde(default)]
+    pub shots: Vec<Shot>,
+    #[serde(default)]
+    pub awards: Vec<Award>,
+    #[serde(default)]
+    pub voting: Option<Voting>,
+    #[serde(default)]
+    pub incidents: Vec<Incident>,
+    #[serde(default)]
+    pub notes: Vec<Note>,
+    #[serde(default)]
+    pub opponent: String,
+    /// fixture details, set ahead of the game.
+    #[serde(default)]
+    pub scheduled_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub venue: String,
+    #[serde(default)]
+    pub round: String,
+    /// counts the saves of the game, so calendars can tell a newer copy of it.
+    #[serde(default)]
+    pub revision: u32,
+    #[serde(default)]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+/// `Fixture` is who, when and where a game is played, entered ahead of match day.
+#[derive(Clone, Debug, Default)]
+pub struct Fixture {
+    pub opponent: String,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub venue: String,
+    pub round: String,
+}
+
+impl Data {
+    /// `touch` marks the game as changed, called each time it is saved.
+    pub fn touch(&mut self) {
+        self.revision += 1;
+        self.updated_at = Some(Utc::now());
+    }
+
+    pub fn set_fixture(&mut self, fixture: Fixture) {
+        self.opponent = fixture.opponent.trim().to_string();
+        self.scheduled_at = fixture.scheduled_at;
+        self.venue = fixture.venue.trim().to_string();
+        self.round = fixture.round.trim().to_string();
+    }
+
+    /// `remap_players` changes every player id in the game using `ids`, eg: when merging games
+    /// from another machine where the same id is a different player. Ids not in `ids` are kept.
+    pub fn remap_players(&mut self, ids: &HashMap<u32, u32>) {
+        let remap = |id: &mut u32| {
+            if let Some(new) = ids.get(id) {
+                *id = *new;
+            }
+        };
+
+        self.players.iter_mut().for_each(|p| remap(&mut p.id));
+        self.mvp.iter_mut().for_each(remap);
+        self.fouls.iter_mut().for_each(|f| remap(&mut f.player_id));
+        self.cards.iter_mut().for_each(|c| remap(&mut c.player_id));
+        self.substitutions
+            .iter_mut()
+            .for_each(|s| remap(&mut s.player_id));
+        self.scores
+            .iter_mut()
+            .for_each(|s| s.player_id.iter_mut().for_each(remap));
+        self.shots.iter_mut().for_each(|s| remap(&mut s.player_id));
+        self.awards.iter_mut().for_each(|a| remap(&mut a.player_id));
+        self.incidents
+            .iter_mut()
+            .for_each(|i| remap(&mut i.player_id));
+        self.notes
+            .iter_mut()
+            .for_each(|n| n.player_id.iter_mut().for_each(remap));
+        self.voting
+            .iter_mut()
+            .flat_map(|v| v.votes.iter_mut())
+            .for_each(|v| remap(&mut v.player_id));
+    }
 }
 
 impl Period {
@@ -29,6 +247,16 @@ impl Period {
     pub const fn finish(&mut self, end_time: DateTime<Utc>) {
         self.end_time = Some(end_time);
     }
+
+    /// `overlap` returns how much of the period falls between `from` and `until`, `None` meaning
+    /// unbounded. A period still in progress is treated as ending now.
+    pub fn overlap(&self, from: Option<DateTime<Utc>>, until: Option<DateTime<Utc>>) -> TimeDelta {
+        let start = from.map_or(self.start_time, |f| f.max(self.start_time));
+        let end = self.end_time.unwrap_or_else(Utc::now);
+        let end = until.map_or(end, |u| u.min(end));
+
+        (end - start).max(TimeDelta::zero())
+    }
 }
 
 impl Default for Period {
@@ -39,3 +267,57 @@ impl Default for Period {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minutes: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 10, minutes, 0).unwrap()
+    }
+
+    #[test]
+    fn test_period_overlap() {
+        let period = Period {
+            start_time: at(10),
+            end_time: Some(at(20)),
+        };
+
+        assert_eq!(period.overlap(None, None), TimeDelta::minutes(10));
+        assert_eq!(period.overlap(Some(at(15)), None), TimeDelta::minutes(5));
+        assert_eq!(period.overlap(None, Some(at(12))), TimeDelta::minutes(2));
+        assert_eq!(
+            period.overlap(Some(at(5)), Some(at(25))),
+            TimeDelta::minutes(10)
+        );
+        assert_eq!(
+            period.overlap(Some(at(12)), Some(at(18))),
+            TimeDelta::minutes(6)
+        );
+
+        // touching or outside the period, or a window that ends before it starts.
+        assert_eq!(period.overlap(Some(at(20)), None), TimeDelta::zero());
+        assert_eq!(period.overlap(None, Some(at(10))), TimeDelta::zero());
+        assert_eq!(period.overlap(Some(at(0)), Some(at(5))), TimeDelta::zero());
+        assert_eq!(
+            period.overlap(Some(at(18)), Some(at(12))),
+            TimeDelta::zero()
+        );
+    }
+
+    #[test]
+    fn test_period_overlap_in_progress() {
+        let period = Period {
+            start_time: Utc::now() - TimeDelta::minutes(10),
+            end_time: None,
+        };
+
+        let overlap = period.overlap(None, None);
+        assert!(overlap >= TimeDelta::minutes(10) && overlap < TimeDelta::minutes(11));
+        assert_eq!(
+            period.overlap(None, Some(period.start_time + TimeDelta::minutes(4))),
+            TimeDelta::minutes(4)
+        );
+    }
+}
@@ -1,6 +1,7 @@
-use super::data::Data;
+use super::data::{Data, Team};
 use super::state::{
-    FinishedState, GamePhase, InProgressState, NotStartedState, PausedState, State,
+    AbandonedState, CancelledState, FinishedState, ForfeitedState, GamePhase, InProgressState,
+    NotStartedState, PausedState, PostponedState, State,
 };
 
 /// `Event` represents an event that has happened affecting the game state.
@@ -9,10 +10,16 @@ pub enum Event {
     EndGame,
     StartPeriod,
     EndPeriod,
+    Cancel,
+    Postpone,
+    Reschedule,
+    Forfeit(Team), // the team forfeiting.
+    Abandon,
     // SubPlayer, // SubPlayer(player_id: u3) ?
 }
 
 /// `EventError` represents errors that can occur when processing events for a game.
+#[derive(Debug, PartialEq, Eq)]
 pub enum EventError {
     NoOp,
     Invalid,
@@ -37,6 +44,18 @@ impl EventHandler for GamePhase<NotStartedState> {
 
                 Ok((next.into(), updated))
             }
+            Event::Cancel => {
+                let (next, updated) = self.cancel(shared);
+                Ok((next.into(), updated))
+            }
+            Event::Postpone => {
+                let (next, updated) = self.postpone(shared);
+                Ok((next.into(), updated))
+            }
+            Event::Forfeit(by) => {
+                let (next, updated) = self.forfeit(shared, by);
+                Ok((next.into(), updated))
+            }
             _ => Err(EventError::Invalid),
         }
     }
@@ -53,6 +72,10 @@ impl EventHandler for GamePhase<InProgressState> {
                 let (next, updated) = self.end_game(shared);
                 Ok((next.into(), updated))
             }
+            Event::Abandon => {
+                let (next, updated) = self.abandon(shared);
+                Ok((next.into(), updated))
+            }
             _ => Err(EventError::Invalid),
         }
     }
@@ -69,6 +92,10 @@ impl EventHandler for GamePhase<PausedState> {
                 let (next, updated) = self.end_game(shared);
                 Ok((next.into(), updated))
             }
+            Event::Abandon => {
+                let (next, updated) = self.abandon(shared);
+                Ok((next.into(), updated))
+            }
             _ => Err(EventError::Invalid),
         }
     }
@@ -79,3 +106,166 @@ impl EventHandler for GamePhase<FinishedState> {
         Err(EventError::Invalid)
     }
 }
+
+impl EventHandler for GamePhase<PostponedState> {
+    fn on_event(self, event: Event, shared: Data) -> Result<(State, Data), EventError> {
+        match event {
+            Event::Reschedule => {
+                let (next, updated) = self.reschedule(shared);
+                Ok((next.into(), updated))
+            }
+            Event::Cancel => {
+                let (next, updated) = self.cancel(shared);
+                Ok((next.into(), updated))
+            }
+            Event::Postpone => Err(EventError::NoOp),
+            _ => Err(EventError::Invalid),
+        }
+    }
+}
+
+impl EventHandler for GamePhase<CancelledState> {
+    fn on_event(self, event: Event, _shared: Data) -> Result<(State, Data), EventError> {
+        match event {
+            Event::Cancel => Err(EventError::NoOp),
+            _ => Err(EventError::Invalid),
+        }
+    }
+}
+
+impl EventHandler for GamePhase<ForfeitedState> {
+    fn on_event(self, event: Event, _shared: Data) -> Result<(State, Data), EventError> {
+        match event {
+            Event::Forfeit(_) => Err(EventError::NoOp),
+            _ => Err(EventError::Invalid),
+        }
+    }
+}
+
+impl EventHandler for GamePhase<AbandonedState> {
+    fn on_event(self, event: Event, _shared: Data) -> Result<(State, Data), EventError> {
+        match event {
+            Event::Abandon => Err(EventError::NoOp),
+            _ => Err(EventError::Invalid),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::GameState;
+    use crate::{Game, Settings};
+
+    // game_in returns a game after applying `events` to a new game.
+    fn game_in(events: Vec<Event>) -> Game {
+        events
+            .into_iter()
+            .try_fold(Game::new(1, vec![], Settings::default()), |g, e| {
+                g.on_event(e)
+            })
+            .unwrap_or_else(|e| panic!("events should apply: {:?}", e))
+    }
+
+    fn kind_after(events: Vec<Event>, event: Event) -> Result<GameState, EventError> {
+        game_in(events).on_event(event).map(|g| g.state.kind())
+    }
+
+    #[test]
+    fn test_not_started() {
+        assert_eq!(kind_after(vec![], Event::Cancel), Ok(GameState::Cancelled));
+        assert_eq!(
+            kind_after(vec![], Event::Postpone),
+            Ok(GameState::Postponed)
+        );
+        assert_eq!(
+            kind_after(vec![], Event::StartGame),
+            Ok(GameState::InProgress)
+        );
+        let game = game_in(vec![Event::Forfeit(Team::Them)]);
+        assert_eq!(game.state.kind(), GameState::Forfeited);
+        assert_eq!(game.state.forfeited_by(), Some(Team::Them));
+
+        for event in [Event::EndGame, Event::Abandon, Event::Reschedule] {
+            assert_eq!(kind_after(vec![], event), Err(EventError::Invalid));
+        }
+    }
+
+    #[test]
+    fn test_postponed() {
+        let postponed = || vec![Event::Postpone];
+        assert_eq!(
+            kind_after(postponed(), Event::Reschedule),
+            Ok(GameState::NotStarted)
+        );
+        assert_eq!(
+            kind_after(postponed(), Event::Cancel),
+            Ok(GameState::Cancelled)
+        );
+        assert_eq!(
+            kind_after(postponed(), Event::Postpone),
+            Err(EventError::NoOp)
+        );
+        assert_eq!(
+            kind_after(postponed(), Event::StartGame),
+            Err(EventError::Invalid)
+        );
+    }
+
+    #[test]
+    fn test_abandon_in_progress() {
+        let game = game_in(vec![Event::StartGame, Event::Abandon]);
+        assert_eq!(game.state.kind(), GameState::Abandoned);
+        assert!(game.state.end_time().is_some());
+        // the period being played stops.
+        assert_eq!(game.shared.periods.len(), 1);
+        assert!(game.shared.periods[0].end_time.is_some());
+    }
+
+    #[test]
+    fn test_abandon_paused() {
+        let paused = game_in(vec![Event::StartGame, Event::EndPeriod]);
+        let period_end = paused.shared.periods[0].end_time;
+
+        let game = paused.on_event(Event::Abandon).unwrap_or_else(|_| panic!());
+        assert_eq!(game.state.kind(), GameState::Abandoned);
+        assert_eq!(game.shared.periods.len(), 1);
+        assert_eq!(game.shared.periods[0].end_time, period_end);
+    }
+
+    #[test]
+    fn test_repeat_is_noop() {
+        assert_eq!(
+            kind_after(vec![Event::Cancel], Event::Cancel),
+            Err(EventError::NoOp)
+        );
+        assert_eq!(
+            kind_after(vec![Event::Forfeit(Team::Us)], Event::Forfeit(Team::Them)),
+            Err(EventError::NoOp)
+        );
+        assert_eq!(
+            kind_after(vec![Event::StartGame, Event::Abandon], Event::Abandon),
+            Err(EventError::NoOp)
+        );
+    }
+
+    #[test]
+    fn test_ended_is_invalid() {
+        assert_eq!(
+            kind_after(vec![Event::Cancel], Event::StartGame),
+            Err(EventError::Invalid)
+        );
+        assert_eq!(
+            kind_after(vec![Event::Forfeit(Team::Us)], Event::Cancel),
+            Err(EventError::Invalid)
+        );
+        assert_eq!(
+            kind_after(vec![Event::StartGame, Event::Abandon], Event::EndGame),
+            Err(EventError::Invalid)
+        );
+        assert_eq!(
+            kind_after(vec![Event::StartGame, Event::EndGame], Event::Abandon),
+            Err(EventError::Invalid)
+        );
+    }
+}
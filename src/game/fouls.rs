@@ -0,0 +1,144 @@
+use super::core::Game;
+use super::data::Foul;
+use chrono::Utc;
+
+impl Game {
+    /// `record_foul` adds a personal foul for the player in the current period, subbing them off
+    /// if they have fouled out. Returns `false` when no period is being played.
+    pub fn record_foul(&mut self, player_id: &u32) -> bool {
+        let Some(period) = self.current_period() else {
+            return false;
+        };
+
+        self.shared.fouls.push(Foul {
+            player_id: *player_id,
+            period,
+            time: Utc::now(),
+        });
+
+        if self.fouled_out(player_id)
+            && let Some(p) = self.shared.players.iter_mut().find(|p| &p.id == player_id)
+        {
+            p.sub_off();
+        }
+
+        true
+    }
+
+    /// `remove_foul` removes the player's most recent foul, correcting a mis-tap.
+    pub fn remove_foul(&mut self, player_id: &u32) -> bool {
+        self.shared
+            .fouls
+            .iter()
+            .rposition(|f| &f.player_id == player_id)
+            .map(|i| self.shared.fouls.remove(i))
+            .is_some()
+    }
+
+    pub fn player_fouls(&self, player_id: &u32) -> u32 {
+        self.shared
+            .fouls
+            .iter()
+            .filter(|f| &f.player_id == player_id)
+            .count() as u32
+    }
+
+    pub fn fouled_out(&self, player_id: &u32) -> bool {
+        let limit = self.shared.settings.foul_out_limit;
+        limit > 0 && self.player_fouls(player_id) >= limit
+    }
+
+    /// `team_fouls` counts the fouls committed in the 1 based `period`.
+    pub fn team_fouls(&self, period: usize) -> u32 {
+        self.shared
+            .fouls
+            .iter()
+            .filter(|f| f.period == period)
+            .count() as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Event, GameView};
+    use crate::{Player, Settings};
+
+    fn in_progress(foul_out_limit: u32, team_foul_bonus: u32) -> Game {
+        let players = (1..=2)
+            .map(|id| Player::new(id, id, id.to_string()))
+            .collect();
+        let settings = Settings {
+            foul_out_limit,
+            team_foul_bonus,
+            ..Settings::default()
+        };
+        Game::new(1, players, settings)
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"))
+    }
+
+    #[test]
+    fn test_fouled_out_at_limit() {
+        let mut game = in_progress(2, 0);
+        game.shared.players[0].sub_on();
+
+        assert!(game.record_foul(&1));
+        assert!(!game.fouled_out(&1));
+        assert!(game.shared.players[0].is_playing());
+
+        assert!(game.record_foul(&1));
+        assert!(game.fouled_out(&1));
+        assert!(!game.shared.players[0].is_playing());
+        assert!(!game.fouled_out(&2));
+
+        // correcting a mis-tap takes them back under the limit.
+        assert!(game.remove_foul(&1));
+        assert!(!game.fouled_out(&1));
+        assert!(!game.remove_foul(&2));
+    }
+
+    #[test]
+    fn test_no_foul_out_limit() {
+        let mut game = in_progress(0, 0);
+        for _ in 0..10 {
+            assert!(game.record_foul(&1));
+        }
+        assert_eq!(game.player_fouls(&1), 10);
+        assert!(!game.fouled_out(&1));
+    }
+
+    #[test]
+    fn test_foul_needs_period() {
+        let mut game = in_progress(5, 0)
+            .on_event(Event::EndPeriod)
+            .unwrap_or_else(|_| panic!("period should end"));
+        assert!(!game.record_foul(&1));
+        assert_eq!(game.player_fouls(&1), 0);
+    }
+
+    #[test]
+    fn test_team_fouls_bonus() {
+        let mut game = in_progress(0, 2);
+        assert!(game.record_foul(&1));
+        assert!(!GameView::from(&game).in_bonus());
+        assert!(game.record_foul(&2));
+        assert_eq!(game.team_fouls(1), 2);
+        assert!(GameView::from(&game).in_bonus());
+
+        // team fouls reset each period.
+        game = game
+            .on_event(Event::EndPeriod)
+            .and_then(|g| g.on_event(Event::StartPeriod))
+            .unwrap_or_else(|_| panic!("next period should start"));
+        assert!(game.record_foul(&1));
+        assert_eq!(game.team_fouls(1), 2);
+        assert_eq!(game.team_fouls(2), 1);
+        assert!(!GameView::from(&game).in_bonus());
+
+        // no bonus when it isn't set.
+        let mut game = in_progress(0, 0);
+        assert!(game.record_foul(&1));
+        assert!(!GameView::from(&game).in_bonus());
+    }
+}
@@ -0,0 +1,115 @@
+use super::core::Game;
+use super::data::Incident;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// `IncidentEntry` is an `Incident` with the details needed to report it.
+#[derive(Clone, Debug)]
+pub struct IncidentEntry {
+    pub index: usize,
+    pub game_id: u32,
+    pub time: DateTime<Utc>,
+    pub period: usize,
+    /// time into the game and into the period, breaks excluded.
+    pub game_time: TimeDelta,
+    pub period_time: TimeDelta,
+    pub player: String,
+    pub description: String,
+    pub returned: bool,
+}
+
+impl IncidentEntry {
+    pub fn game_clock(&self) -> String {
+        let clock = |d: TimeDelta| format!("{}:{:02}", d.num_minutes(), d.num_seconds() % 60);
+        format!(
+            "{} (period {})",
+            clock(self.game_time),
+            clock(self.period_time)
+        )
+    }
+}
+
+impl Game {
+    /// `record_incident` logs an incident for the player in the latest period, subbing them off.
+    /// Returns `false` before the game has started.
+    pub fn record_incident(&mut self, player_id: &u32, description: &str) -> bool {
+        if self.shared.periods.is_empty() {
+            return false;
+        }
+
+        self.shared.incidents.push(Incident {
+            player_id: *player_id,
+            period: self.shared.periods.len(),
+            time: Utc::now(),
+            description: description.to_string(),
+        });
+
+        if let Some(p) = self.shared.players.iter_mut().find(|p| &p.id == player_id) {
+            p.sub_off();
+        }
+
+        true
+    }
+
+    /// `remove_incident` removes the incident at `index`, correcting a mistake.
+    pub fn remove_incident(&mut self, index: usize) -> bool {
+        if index >= self.shared.incidents.len() {
+            return false;
+        }
+
+        self.shared.incidents.remove(index);
+        true
+    }
+
+    /// `returned` is true when the player went back on court after the incident.
+    pub fn returned(&self, incident: &Incident) -> bool {
+        self.shared
+            .players
+            .iter()
+            .find(|p| p.id == incident.player_id)
+            .is_some_and(|p| {
+                p.stints.iter().any(|s| s.start_time > incident.time)
+                    || p.play_start_time.is_some_and(|st| st > incident.time)
+            })
+    }
+
+    /// `game_clock` returns how far into the game and into the period `time` was, breaks excluded.
+    pub fn game_clock(&self, time: DateTime<Utc>) -> (TimeDelta, TimeDelta) {
+        let game_time = self
+            .shared
+            .periods
+            .iter()
+            .map(|p| p.overlap(None, Some(time)))
+            .sum();
+        let period_time = self
+            .shared
+            .periods
+            .iter()
+            .rev()
+            .find(|p| p.start_time <= time)
+            .map_or(TimeDelta::zero(), |p| p.overlap(None, Some(time)));
+
+        (game_time, period_time)
+    }
+
+    pub fn incident_entries(&self) -> Vec<IncidentEntry> {
+        self.shared
+            .incidents
+            .iter()
+            .enumerate()
+            .map(|(index, incident)| {
+                let (game_time, period_time) = self.game_clock(incident.time);
+                IncidentEntry {
+                    index,
+                    game_id: self.id,
+                    time: incident.time,
+                    period: incident.period,
+                    game_time,
+                    period_time,
+                    player: self.player_label(&incident.player_id),
+                    description: incident.description.clone(),
+                    returned: self.returned(incident),
+                }
+            })
+            .collect()
+    }
+}
@@ -1,15 +1,34 @@
 //! `game` contains the main `Game` struct, events and other items that can be interacted with to manage a sports game.
 
+mod awards;
+mod cards;
 mod core;
 mod data;
 mod event;
+mod fouls;
+mod incidents;
+mod notes;
+mod participation;
+mod scores;
+mod shots;
 mod state;
+mod subs;
+mod timeline;
 mod view;
+mod voting;
 
 // re-export some objects to reduce use import stuttering.
 pub use core::Game;
-pub use data::Data;
+pub use data::{
+    Award, Card, CardKind, Data, Fixture, Foul, Incident, Note, Score, Shot, Substitution, Team,
+    Vote, Voting,
+};
 pub use event::{Event, EventError};
+pub use incidents::IncidentEntry;
+pub use notes::NoteEntry;
+pub use participation::Participation;
+pub use scores::{LINEUP_SIZE, Lineup};
 pub use state::GameState;
 pub use state::State;
+pub use timeline::TimelineEntry;
 pub use view::{GameView, into_game_views};
@@ -0,0 +1,57 @@
+use super::core::Game;
+use super::data::Note;
+use chrono::{DateTime, Utc};
+
+/// `NoteEntry` is a `Note` with the details needed to list it outside of its game.
+#[derive(Clone, Debug)]
+pub struct NoteEntry {
+    pub index: usize,
+    pub game_id: u32,
+    pub player_id: Option<u32>,
+    /// player label, `None` for notes about the game.
+    pub player: Option<String>,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub period: usize,
+    pub time: DateTime<Utc>,
+}
+
+impl Game {
+    /// `add_note` adds a note about the game, or about a player when `player_id` is set.
+    pub fn add_note(&mut self, player_id: Option<u32>, text: &str, tags: Vec<String>) {
+        self.shared.notes.push(Note {
+            player_id,
+            text: text.to_string(),
+            tags,
+            period: self.shared.periods.len(),
+            time: Utc::now(),
+        });
+    }
+
+    pub fn remove_note(&mut self, index: usize) -> bool {
+        if index >= self.shared.notes.len() {
+            return false;
+        }
+
+        self.shared.notes.remove(index);
+        true
+    }
+
+    pub fn note_entries(&self) -> Vec<NoteEntry> {
+        self.shared
+            .notes
+            .iter()
+            .enumerate()
+            .map(|(index, note)| NoteEntry {
+                index,
+                game_id: self.id,
+                player_id: note.player_id,
+                player: note.player_id.map(|id| self.player_label(&id)),
+                text: note.text.clone(),
+                tags: note.tags.clone(),
+                period: note.period,
+                time: note.time,
+            })
+            .collect()
+    }
+}
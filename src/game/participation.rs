@@ -0,0 +1,193 @@
+use super::core::Game;
+use super::state::GameState;
+use chrono::{DateTime, TimeDelta, Utc};
+
+/// `Participation` is a player's minutes in each period of a game, checked against the minimum
+/// participation settings.
+#[derive(Clone, Debug)]
+pub struct Participation {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    /// false for a late player that never arrived.
+    pub attended: bool,
+    pub period_played: Vec<TimeDelta>,
+    pub played: TimeDelta,
+    /// `None` when the player wasn't at the game or no minimums are set.
+    pub compliant: Option<bool>,
+}
+
+impl Participation {
+    pub fn periods_played(&self) -> u32 {
+        self.period_played
+            .iter()
+            .filter(|p| **p > TimeDelta::zero())
+            .count() as u32
+    }
+}
+
+impl Game {
+    /// `participation` splits each player's time on court by period and checks it meets the
+    /// game's minimum periods and minutes settings. Minimums aren't checked for an abandoned
+    /// game, players couldn't have met them.
+    pub fn participation(&self) -> Vec<Participation> {
+        let now = Utc::now();
+        let settings = &self.shared.settings;
+        let enforced = (settings.min_periods > 0 || settings.min_minutes > 0)
+            && self.state.kind() != GameState::Abandoned;
+
+        self.shared
+            .players
+            .iter()
+            .map(|player| {
+                let mut stints: Vec<(DateTime<Utc>, DateTime<Utc>)> = player
+                    .stints
+                    .iter()
+                    .map(|s| (s.start_time, s.end_time))
+                    .collect();
+                if let Some(st) = player.play_start_time {
+                    stints.push((st, now));
+                }
+
+                let period_played: Vec<TimeDelta> = self
+                    .shared
+                    .periods
+                    .iter()
+                    .map(|period| {
+                        stints
+                            .iter()
+                            .map(|(start, end)| period.overlap(Some(*start), Some(*end)))
+                            .sum()
+                    })
+                    .collect();
+
+                let mut participation = Participation {
+                    player_id: player.id,
+                    number: player.number,
+                    name: player.name.clone(),
+                    attended: player.availability().is_some(),
+                    played: period_played.iter().sum(),
+                    period_played,
+                    compliant: None,
+                };
+                if participation.attended && enforced {
+                    participation.compliant = Some(
+                        participation.periods_played() >= settings.min_periods
+                            && participation.played
+                                >= TimeDelta::minutes(settings.min_minutes.into()),
+                    );
+                }
+
+                participation
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::game::data::Period;
+    use crate::player::Stint;
+    use crate::{Player, Settings};
+
+    // played returns a game with two ten minute periods, player 1 on for eight minutes of the
+    // first and two of the second, player 2 on for three minutes of the first and player 3 late.
+    fn played(settings: Settings) -> Game {
+        let start = Utc::now() - TimeDelta::hours(1);
+        let at = |minutes| start + TimeDelta::minutes(minutes);
+        let mut players: Vec<Player> = (1..=3)
+            .map(|id| Player::new(id, id, id.to_string()))
+            .collect();
+        players[0].stints = vec![Stint {
+            start_time: at(2),
+            end_time: at(12),
+        }];
+        players[1].stints = vec![Stint {
+            start_time: at(1),
+            end_time: at(4),
+        }];
+        players[2].mark_late();
+
+        let mut game = Game::new(1, players, settings);
+        game.shared.periods = vec![
+            Period {
+                start_time: at(0),
+                end_time: Some(at(10)),
+            },
+            Period {
+                start_time: at(10),
+                end_time: Some(at(20)),
+            },
+        ];
+
+        game
+    }
+
+    fn minimums() -> Settings {
+        Settings {
+            min_periods: 2,
+            min_minutes: 5,
+            ..Settings::default()
+        }
+    }
+
+    #[test]
+    fn test_participation_by_period() {
+        let participation = played(minimums()).participation();
+
+        assert_eq!(
+            participation[0].period_played,
+            vec![TimeDelta::minutes(8), TimeDelta::minutes(2)]
+        );
+        assert_eq!(participation[0].played, TimeDelta::minutes(10));
+        assert_eq!(participation[0].periods_played(), 2);
+        assert_eq!(participation[1].played, TimeDelta::minutes(3));
+        assert_eq!(participation[1].periods_played(), 1);
+        assert_eq!(participation[2].played, TimeDelta::zero());
+    }
+
+    #[test]
+    fn test_participation_compliance() {
+        let participation = played(minimums()).participation();
+        assert_eq!(participation[0].compliant, Some(true));
+        assert_eq!(participation[1].compliant, Some(false));
+        assert!(!participation[2].attended);
+        assert_eq!(participation[2].compliant, None);
+
+        // exactly the minimum minutes is enough, one period short isn't.
+        let participation = played(Settings {
+            min_periods: 2,
+            min_minutes: 10,
+            ..Settings::default()
+        })
+        .participation();
+        assert_eq!(participation[0].compliant, Some(true));
+        let participation = played(Settings {
+            min_periods: 3,
+            ..Settings::default()
+        })
+        .participation();
+        assert_eq!(participation[0].compliant, Some(false));
+    }
+
+    #[test]
+    fn test_participation_not_enforced() {
+        let participation = played(Settings::default()).participation();
+        assert!(participation.iter().all(|p| p.compliant.is_none()));
+
+        let mut game = played(minimums());
+        let periods = game.shared.periods.clone();
+        let players = game.shared.players.clone();
+        game = game
+            .on_event(Event::StartGame)
+            .and_then(|g| g.on_event(Event::Abandon))
+            .unwrap_or_else(|_| panic!("game should be abandoned"));
+        game.shared.periods = periods;
+        game.shared.players = players;
+        let participation = game.participation();
+        assert_eq!(participation[0].played, TimeDelta::minutes(10));
+        assert!(participation.iter().all(|p| p.compliant.is_none()));
+    }
+}
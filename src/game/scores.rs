@@ -0,0 +1,274 @@
+use super::core::Game;
+use super::data::{Score, Team};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// `LINEUP_SIZE` is how many players make up a lineup when comparing combinations.
+pub const LINEUP_SIZE: usize = 5;
+
+/// `Lineup` is a combination of players and the points scored while they were on court together.
+#[derive(Clone, Debug, Default)]
+pub struct Lineup {
+    pub player_ids: Vec<u32>, // sorted, to compare lineups.
+    pub players: Vec<String>,
+    pub points_for: u32,
+    pub points_against: u32,
+}
+
+impl Lineup {
+    pub fn plus_minus(&self) -> i64 {
+        i64::from(self.points_for) - i64::from(self.points_against)
+    }
+}
+
+// combinations returns every `size` player combination of `ids`, or all of `ids` when there are
+// too few players to choose from.
+fn combinations(ids: &[u32], size: usize) -> Vec<Vec<u32>> {
+    if ids.len() <= size {
+        return vec![ids.to_vec()];
+    }
+
+    let mut combinations = vec![];
+    choose(ids, size, &mut vec![], &mut combinations);
+    combinations
+}
+
+fn choose(ids: &[u32], size: usize, current: &mut Vec<u32>, combinations: &mut Vec<Vec<u32>>) {
+    if current.len() == size {
+        combinations.push(current.clone());
+        return;
+    }
+
+    for (i, id) in ids.iter().enumerate() {
+        current.push(*id);
+        choose(&ids[i + 1..], size, current, combinations);
+        current.pop();
+    }
+}
+
+impl Game {
+    /// `record_score` adds points for the team in the current period. Returns `false` when no
+    /// period is being played.
+    pub fn record_score(&mut self, team: Team, player_id: Option<u32>, points: u32) -> bool {
+        let Some(period) = self.current_period() else {
+            return false;
+        };
+
+        self.shared.scores.push(Score {
+            team,
+            player_id,
+            points,
+            period,
+            time: Utc::now(),
+        });
+
+        true
+    }
+
+    /// `remove_score` removes the most recent score for the team and scorer, correcting a mis-tap.
+    pub fn remove_score(&mut self, team: Team, player_id: Option<u32>) -> bool {
+        self.shared
+            .scores
+            .iter()
+            .rposition(|s| s.team == team && s.player_id == player_id)
+            .map(|i| self.shared.scores.remove(i))
+            .is_some()
+    }
+
+    pub fn score(&self, team: Team) -> u32 {
+        self.shared
+            .scores
+            .iter()
+            .filter(|s| s.team == team)
+            .map(|s| s.points)
+            .sum()
+    }
+
+    pub fn player_points(&self, player_id: &u32) -> u32 {
+        self.shared
+            .scores
+            .iter()
+            .filter(|s| s.player_id.as_ref() == Some(player_id))
+            .map(|s| s.points)
+            .sum()
+    }
+
+    /// `on_court_at` returns the ids of the players on court at `time`, sorted.
+    pub fn on_court_at(&self, time: DateTime<Utc>) -> Vec<u32> {
+        let mut ids: Vec<u32> = self
+            .shared
+            .players
+            .iter()
+            .filter(|p| p.on_court_at(time))
+            .map(|p| p.id)
+            .collect();
+
+        ids.sort();
+        ids
+    }
+
+    /// `plus_minus` is the points for less the points against while the player was on court.
+    pub fn plus_minus(&self, player_id: &u32) -> i64 {
+        self.shared
+            .scores
+            .iter()
+            .filter(|s| self.on_court_at(s.time).contains(player_id))
+            .map(|s| match s.team {
+                Team::Us => i64::from(s.points),
+                Team::Them => -i64::from(s.points),
+            })
+            .sum()
+    }
+
+    /// `lineups` totals the points for and against each `LINEUP_SIZE` combination of players on
+    /// court when a score happened, best plus/minus first. There can be many combinations, so
+    /// only call it where lineups are shown.
+    pub fn lineups(&self) -> Vec<Lineup> {
+        // keyed by the sorted player ids, `on_court_at` sorts them.
+        let mut lineups: HashMap<Vec<u32>, Lineup> = HashMap::new();
+
+        for score in &self.shared.scores {
+            let on_court = self.on_court_at(score.time);
+            if on_court.is_empty() {
+                continue;
+            }
+
+            for player_ids in combinations(&on_court, LINEUP_SIZE) {
+                let lineup = lineups.entry(player_ids).or_insert_with_key(|ids| Lineup {
+                    players: ids.iter().map(|id| self.player_label(id)).collect(),
+                    player_ids: ids.clone(),
+                    ..Lineup::default()
+                });
+
+                match score.team {
+                    Team::Us => lineup.points_for += score.points,
+                    Team::Them => lineup.points_against += score.points,
+                }
+            }
+        }
+
+        let mut lineups: Vec<Lineup> = lineups.into_values().collect();
+        lineups.sort_by_key(|l| (std::cmp::Reverse(l.plus_minus()), l.player_ids.clone()));
+        lineups
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::player::Stint;
+    use crate::{Player, Settings};
+    use chrono::TimeZone;
+
+    fn at(minutes: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 10, minutes, 0).unwrap()
+    }
+
+    // game returns a game of players on court for the `stints` in minutes, by player id from 1.
+    fn game(stints: &[&[(u32, u32)]]) -> Game {
+        let players = stints
+            .iter()
+            .zip(1..)
+            .map(|(stints, id)| Player {
+                stints: stints
+                    .iter()
+                    .map(|(start, end)| Stint {
+                        start_time: at(*start),
+                        end_time: at(*end),
+                    })
+                    .collect(),
+                ..Player::new(id, id, id.to_string())
+            })
+            .collect();
+
+        Game::new(1, players, Settings::default())
+    }
+
+    fn score(game: &mut Game, team: Team, points: u32, minutes: u32) {
+        game.shared.scores.push(Score {
+            team,
+            player_id: None,
+            points,
+            period: 1,
+            time: at(minutes),
+        });
+    }
+
+    #[test]
+    fn test_plus_minus_on_court() {
+        let mut game = game(&[&[(0, 10)], &[(10, 20)]]);
+        score(&mut game, Team::Us, 2, 3);
+        score(&mut game, Team::Them, 3, 8);
+        score(&mut game, Team::Us, 3, 15);
+
+        assert_eq!(game.plus_minus(&1), -1);
+        assert_eq!(game.plus_minus(&2), 3);
+
+        // still on court counts too.
+        game.shared.players[1].play_start_time = Some(at(25));
+        score(&mut game, Team::Them, 2, 30);
+        assert_eq!(game.plus_minus(&2), 1);
+        assert_eq!(game.plus_minus(&1), -1);
+    }
+
+    #[test]
+    fn test_plus_minus_at_boundary() {
+        // the first basket of the second period, scored as it starts. 2 replaces 1 for it and
+        // 3 stays on over the break.
+        let mut game = game(&[&[(0, 10)], &[(10, 20)], &[(0, 30)]]);
+        score(&mut game, Team::Us, 2, 10);
+        game.shared.scores[0].period = 2;
+        assert_eq!(game.on_court_at(at(10)), vec![2, 3]);
+        assert_eq!(game.plus_minus(&1), 0);
+        assert_eq!(game.plus_minus(&2), 2);
+        assert_eq!(game.plus_minus(&3), 2);
+
+        // a basket after everyone went off counts for nobody.
+        score(&mut game, Team::Them, 2, 30);
+        assert!(game.on_court_at(at(30)).is_empty());
+        assert_eq!(game.plus_minus(&3), 2);
+        assert!(game.lineups().iter().all(|l| l.points_against == 0));
+    }
+
+    #[test]
+    fn test_lineup_changes_mid_period() {
+        // 6 replaces 5 at minute 5 of the period.
+        let mut game = game(&[
+            &[(0, 10)],
+            &[(0, 10)],
+            &[(0, 10)],
+            &[(0, 10)],
+            &[(0, 5)],
+            &[(5, 10)],
+        ]);
+        score(&mut game, Team::Us, 2, 2);
+        score(&mut game, Team::Us, 2, 4);
+        score(&mut game, Team::Them, 3, 7);
+
+        let lineups = game.lineups();
+        assert_eq!(lineups.len(), 2);
+        assert_eq!(lineups[0].player_ids, vec![1, 2, 3, 4, 5]);
+        assert_eq!((lineups[0].points_for, lineups[0].points_against), (4, 0));
+        assert_eq!(lineups[1].player_ids, vec![1, 2, 3, 4, 6]);
+        assert_eq!(lineups[1].plus_minus(), -3);
+        assert_eq!(lineups[1].players[4], "#6 6");
+    }
+
+    #[test]
+    fn test_lineup_combinations() {
+        // six on court at once make six lineups of five.
+        let stints: [&[(u32, u32)]; 6] = [&[(0, 10)]; 6];
+        let mut game = game(&stints);
+        score(&mut game, Team::Us, 2, 5);
+        let lineups = game.lineups();
+        assert_eq!(lineups.len(), 6);
+        assert!(lineups.iter().all(|l| l.player_ids.len() == LINEUP_SIZE));
+        assert!(lineups.iter().all(|l| l.plus_minus() == 2));
+
+        assert_eq!(combinations(&[1, 2], LINEUP_SIZE), vec![vec![1, 2]]);
+        assert_eq!(
+            combinations(&[1, 2, 3], 2),
+            vec![vec![1, 2], vec![1, 3], vec![2, 3]]
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use super::core::Game;
+use super::data::Shot;
+use chrono::Utc;
+
+impl Game {
+    /// `record_shot` adds a shot by the player in the current period, clamping the location to the
+    /// game's surface. Returns `false` when no period is being played.
+    pub fn record_shot(&mut self, player_id: &u32, x: u32, y: u32, made: bool) -> bool {
+        let Some(period) = self.current_period() else {
+            return false;
+        };
+
+        let surface = self.shared.settings.surface;
+        self.shared.shots.push(Shot {
+            player_id: *player_id,
+            x: x.min(surface.width()),
+            y: y.min(surface.length()),
+            made,
+            period,
+            time: Utc::now(),
+        });
+
+        true
+    }
+
+    /// `remove_shot` removes the most recent shot, correcting a mis-tap.
+    pub fn remove_shot(&mut self) -> bool {
+        self.shared.shots.pop().is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::settings::Surface;
+    use crate::{Player, Settings};
+
+    #[test]
+    fn test_record_shot_clamps_to_surface() {
+        for surface in Surface::ALL {
+            let settings = Settings {
+                surface,
+                ..Settings::default()
+            };
+            let mut game = Game::new(1, vec![Player::new(1, 1, "a".to_string())], settings)
+                .on_event(Event::StartGame)
+                .unwrap_or_else(|_| panic!("game should start"));
+
+            assert!(game.record_shot(&1, 500, 500, true));
+            let shot = &game.shared.shots[0];
+            assert_eq!((shot.x, shot.y), (surface.width(), surface.length()));
+        }
+    }
+}
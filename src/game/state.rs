@@ -1,4 +1,4 @@
-use super::data::{Data, Period};
+use super::data::{Data, Period, Team};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
@@ -12,13 +12,64 @@ pub enum State {
     InProgress(GamePhase<InProgressState>),
     Paused(GamePhase<PausedState>),
     Finished(GamePhase<FinishedState>),
+    Cancelled(GamePhase<CancelledState>),
+    Postponed(GamePhase<PostponedState>),
+    Forfeited(GamePhase<ForfeitedState>),
+    Abandoned(GamePhase<AbandonedState>),
 }
 
+/// `GameState` is the kind of `State` a game is in. How each counts towards stats:
+///
+/// - `Finished` and `Abandoned` games add their minutes to player totals, an abandoned game
+///   keeping the minutes played before it stopped, without a result or minimum participation.
+/// - `InProgress` and `Paused` games count towards stats as they're played.
+/// - `Forfeited` games have a result but no minutes or attendance.
+/// - `NotStarted`, `Postponed` and `Cancelled` games don't count.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum GameState {
     NotStarted,
     InProgress,
     Paused,
     Finished,
+    Cancelled,
+    Postponed,
+    Forfeited,
+    Abandoned,
+}
+
+impl GameState {
+    /// `is_played` is true when players' minutes and attendance in the game count towards stats.
+    pub const fn is_played(self) -> bool {
+        matches!(
+            self,
+            Self::InProgress | Self::Paused | Self::Finished | Self::Abandoned
+        )
+    }
+
+    /// `has_ended` is true once a played game is over, its minutes adding to player totals.
+    pub const fn has_ended(self) -> bool {
+        matches!(self, Self::Finished | Self::Abandoned)
+    }
+
+    /// `is_upcoming` is true for games still to be played.
+    pub const fn is_upcoming(self) -> bool {
+        matches!(self, Self::NotStarted | Self::Postponed)
+    }
+}
+
+impl std::fmt::Display for GameState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotStarted => write!(f, "not started"),
+            Self::InProgress => write!(f, "in progress"),
+            Self::Paused => write!(f, "paused"),
+            Self::Finished => write!(f, "finished"),
+            Self::Cancelled => write!(f, "cancelled"),
+            Self::Postponed => write!(f, "postponed"),
+            Self::Forfeited => write!(f, "forfeited"),
+            Self::Abandoned => write!(f, "abandoned"),
+        }
+    }
 }
 
 impl State {
@@ -28,6 +79,38 @@ impl State {
             Self::InProgress(_) => GameState::InProgress,
             Self::Paused(_) => GameState::Paused,
             Self::Finished(_) => GameState::Finished,
+            Self::Cancelled(_) => GameState::Cancelled,
+            Self::Postponed(_) => GameState::Postponed,
+            Self::Forfeited(_) => GameState::Forfeited,
+            Self::Abandoned(_) => GameState::Abandoned,
+        }
+    }
+
+    pub fn start_time(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::NotStarted(_) | Self::Cancelled(_) | Self::Postponed(_) | Self::Forfeited(_) => {
+                None
+            }
+            Self::InProgress(p) => Some(p.state.start_time),
+            Self::Paused(p) => Some(p.state.start_time),
+            Self::Finished(p) => Some(p.state.start_time),
+            Self::Abandoned(p) => Some(p.state.start_time),
+        }
+    }
+
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        match self {
+            Self::Finished(p) => Some(p.state.end_time),
+            Self::Abandoned(p) => Some(p.state.end_time),
+            _ => None,
+        }
+    }
+
+    /// `forfeited_by` is the team that forfeited the game, `Team::Them` being a win.
+    pub fn forfeited_by(&self) -> Option<Team> {
+        match self {
+            Self::Forfeited(p) => Some(p.state.forfeited_by),
+            _ => None,
         }
     }
 }
@@ -49,6 +132,25 @@ pub struct FinishedState {
     pub end_time: DateTime<Utc>,
 }
 
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CancelledState {
+    pub cancelled_at: DateTime<Utc>,
+}
+#[derive(Clone, Serialize, Deserialize)]
+pub struct PostponedState {
+    pub postponed_at: DateTime<Utc>,
+}
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ForfeitedState {
+    pub forfeited_by: Team,
+    pub forfeited_at: DateTime<Utc>,
+}
+#[derive(Clone, Serialize, Deserialize)]
+pub struct AbandonedState {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
 /// `GamePhase` is a marker struct representing the phase or state a game is in and enforces
 /// compile time safety of phase/state transitions.
 #[derive(Clone, Serialize, Deserialize)]
@@ -75,6 +177,37 @@ impl GamePhase<NotStartedState> {
 
         (next, shared)
     }
+
+    pub fn cancel(self, shared: Data) -> (GamePhase<CancelledState>, Data) {
+        let next = GamePhase {
+            state: CancelledState {
+                cancelled_at: Utc::now(),
+            },
+        };
+
+        (next, shared)
+    }
+
+    pub fn postpone(self, shared: Data) -> (GamePhase<PostponedState>, Data) {
+        let next = GamePhase {
+            state: PostponedState {
+                postponed_at: Utc::now(),
+            },
+        };
+
+        (next, shared)
+    }
+
+    pub fn forfeit(self, shared: Data, by: Team) -> (GamePhase<ForfeitedState>, Data) {
+        let next = GamePhase {
+            state: ForfeitedState {
+                forfeited_by: by,
+                forfeited_at: Utc::now(),
+            },
+        };
+
+        (next, shared)
+    }
 }
 
 impl GamePhase<InProgressState> {
@@ -111,6 +244,10 @@ impl GamePhase<InProgressState> {
 
         (next, shared)
     }
+
+    pub fn abandon(self, shared: Data) -> (GamePhase<AbandonedState>, Data) {
+        abandon(self.state.start_time, shared)
+    }
 }
 
 impl GamePhase<PausedState> {
@@ -142,10 +279,52 @@ impl GamePhase<PausedState> {
 
         (next, shared)
     }
+
+    pub fn abandon(self, shared: Data) -> (GamePhase<AbandonedState>, Data) {
+        abandon(self.state.start_time, shared)
+    }
+}
+
+// abandon stops a game started at `start_time` from being played or paused. The period being played
+// stops, keeping the minutes played so far.
+fn abandon(start_time: DateTime<Utc>, mut shared: Data) -> (GamePhase<AbandonedState>, Data) {
+    let end_time = Utc::now();
+    if let Some(mut period) = shared.periods.pop() {
+        if period.end_time.is_none() {
+            period.finish(end_time);
+        }
+        shared.periods.push(period);
+    }
+
+    let next = GamePhase {
+        state: AbandonedState {
+            start_time,
+            end_time,
+        },
+    };
+
+    (next, shared)
 }
 
 impl GamePhase<FinishedState> {}
 
+impl GamePhase<PostponedState> {
+    /// `reschedule` returns a postponed game to be played, its fixture having a new time.
+    pub fn reschedule(self, shared: Data) -> (GamePhase<NotStartedState>, Data) {
+        (GamePhase::default(), shared)
+    }
+
+    pub fn cancel(self, shared: Data) -> (GamePhase<CancelledState>, Data) {
+        let next = GamePhase {
+            state: CancelledState {
+                cancelled_at: Utc::now(),
+            },
+        };
+
+        (next, shared)
+    }
+}
+
 // Support `next.into()` usage in ./event.rs, unwrapping our phase marker struct into the
 // Game struct embedded enum State.
 impl<S> GamePhase<S> {
@@ -180,3 +359,27 @@ impl From<GamePhase<FinishedState>> for State {
         Self::Finished(phase)
     }
 }
+
+impl From<GamePhase<CancelledState>> for State {
+    fn from(phase: GamePhase<CancelledState>) -> Self {
+        Self::Cancelled(phase)
+    }
+}
+
+impl From<GamePhase<PostponedState>> for State {
+    fn from(phase: GamePhase<PostponedState>) -> Self {
+        Self::Postponed(phase)
+    }
+}
+
+impl From<GamePhase<ForfeitedState>> for State {
+    fn from(phase: GamePhase<ForfeitedState>) -> Self {
+        Self::Forfeited(phase)
+    }
+}
+
+impl From<GamePhase<AbandonedState>> for State {
+    fn from(phase: GamePhase<AbandonedState>) -> Self {
+        Self::Abandoned(phase)
+    }
+}
@@ -0,0 +1,220 @@
+use super::core::Game;
+use super::data::Substitution;
+use crate::settings::SubRule;
+use chrono::{TimeDelta, Utc};
+
+// Substitutions within this long of the previous one share its window, eg: several players
+// swapped at the same stoppage.
+const SUB_WINDOW_SECONDS: i64 = 60;
+
+impl Game {
+    /// `is_substitution` is true when subbing on now would replace a player subbed off during the
+    /// current period, rather than choosing the lineup at the start of the period.
+    pub fn is_substitution(&self) -> bool {
+        let Some(period) = self.shared.periods.last().filter(|p| p.end_time.is_none()) else {
+            return false;
+        };
+
+        self.shared
+            .players
+            .iter()
+            .flat_map(|p| &p.stints)
+            .any(|s| s.end_time >= period.start_time)
+    }
+
+    pub fn subs_made(&self) -> u32 {
+        self.shared.substitutions.len() as u32
+    }
+
+    pub fn windows_used(&self) -> u32 {
+        self.shared.substitutions.last().map_or(0, |s| s.window)
+    }
+
+    // open_window returns the window a substitution made now would share, if still open.
+    fn open_window(&self) -> Option<u32> {
+        let period = self.current_period()?;
+
+        self.shared
+            .substitutions
+            .last()
+            .filter(|s| s.period == period)
+            .filter(|s| Utc::now() - s.time <= TimeDelta::seconds(SUB_WINDOW_SECONDS))
+            .map(|s| s.window)
+    }
+
+    /// `check_sub_rules` returns why a substitution can't be made now under the team's
+    /// `SubRule`, if it can't.
+    pub fn check_sub_rules(&self) -> Result<(), String> {
+        let limit = self.shared.settings.sub_limit;
+
+        match self.shared.settings.sub_rule {
+            SubRule::Rolling => Ok(()),
+            SubRule::MaxSubs if self.subs_made() >= limit => Err(format!(
+                "no substitutions remaining, {limit} allowed per game"
+            )),
+            SubRule::MaxSubs => Ok(()),
+            SubRule::MaxWindows if self.open_window().is_none() && self.windows_used() >= limit => {
+                Err(format!(
+                    "no substitution windows remaining, {limit} allowed per game"
+                ))
+            }
+            SubRule::MaxWindows => Ok(()),
+            SubRule::HalfTimeOnly => {
+                Err("substitutions are only allowed between periods".to_string())
+            }
+        }
+    }
+
+    /// `record_substitution` counts the player subbed on towards the team's `SubRule`.
+    pub fn record_substitution(&mut self, player_id: &u32) {
+        let window = self
+            .open_window()
+            .unwrap_or_else(|| self.windows_used() + 1);
+
+        self.shared.substitutions.push(Substitution {
+            player_id: *player_id,
+            period: self.shared.periods.len(),
+            time: Utc::now(),
+            window,
+        });
+    }
+
+    /// `subs_remaining` describes the substitutions left under the team's `SubRule`, `None` when
+    /// unlimited.
+    pub fn subs_remaining(&self) -> Option<String> {
+        let limit = self.shared.settings.sub_limit;
+
+        match self.shared.settings.sub_rule {
+            SubRule::Rolling => None,
+            SubRule::MaxSubs => Some(format!(
+                "{} of {limit} subs remaining",
+                limit.saturating_sub(self.subs_made())
+            )),
+            SubRule::MaxWindows => Some(format!(
+                "{} of {limit} sub windows remaining{}",
+                limit.saturating_sub(self.windows_used()),
+                if self.open_window().is_some() {
+                    ", window open"
+                } else {
+                    ""
+                }
+            )),
+            SubRule::HalfTimeOnly => Some("subs between periods only".to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::{Player, Settings};
+
+    fn in_progress(sub_rule: SubRule, sub_limit: u32) -> Game {
+        let players = (1..=3)
+            .map(|id| Player::new(id, id, id.to_string()))
+            .collect();
+        let settings = Settings {
+            sub_rule,
+            sub_limit,
+            ..Settings::default()
+        };
+        Game::new(1, players, settings)
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"))
+    }
+
+    #[test]
+    fn test_is_substitution() {
+        let mut game = in_progress(SubRule::Rolling, 0);
+        assert!(!game.is_substitution());
+
+        // the starting lineup going on isn't a substitution, replacing one of them is.
+        game.shared.players[0].sub_on();
+        assert!(!game.is_substitution());
+        game.shared.players[0].sub_off();
+        assert!(game.is_substitution());
+
+        // nor is choosing the lineup for the next period.
+        game = game
+            .on_event(Event::EndPeriod)
+            .and_then(|g| g.on_event(Event::StartPeriod))
+            .unwrap_or_else(|_| panic!("next period should start"));
+        assert!(!game.is_substitution());
+    }
+
+    #[test]
+    fn test_max_subs() {
+        let mut game = in_progress(SubRule::MaxSubs, 2);
+        assert_eq!(
+            game.subs_remaining().as_deref(),
+            Some("2 of 2 subs remaining")
+        );
+
+        game.record_substitution(&1);
+        assert_eq!(game.check_sub_rules(), Ok(()));
+        game.record_substitution(&2);
+        assert_eq!(game.subs_made(), 2);
+        assert_eq!(
+            game.check_sub_rules(),
+            Err("no substitutions remaining, 2 allowed per game".to_string())
+        );
+        assert_eq!(
+            game.subs_remaining().as_deref(),
+            Some("0 of 2 subs remaining")
+        );
+    }
+
+    #[test]
+    fn test_max_windows() {
+        let mut game = in_progress(SubRule::MaxWindows, 1);
+        game.record_substitution(&1);
+        game.record_substitution(&2);
+        assert_eq!(game.windows_used(), 1);
+        assert_eq!(game.subs_made(), 2);
+
+        // the window is still open for another sub at the same stoppage.
+        assert_eq!(game.check_sub_rules(), Ok(()));
+        assert_eq!(
+            game.subs_remaining().as_deref(),
+            Some("0 of 1 sub windows remaining, window open")
+        );
+
+        // once it closes no windows are left.
+        for sub in &mut game.shared.substitutions {
+            sub.time -= TimeDelta::seconds(SUB_WINDOW_SECONDS + 1);
+        }
+        assert_eq!(
+            game.check_sub_rules(),
+            Err("no substitution windows remaining, 1 allowed per game".to_string())
+        );
+        assert_eq!(
+            game.subs_remaining().as_deref(),
+            Some("0 of 1 sub windows remaining")
+        );
+    }
+
+    #[test]
+    fn test_window_closes_with_period() {
+        let mut game = in_progress(SubRule::MaxWindows, 2);
+        game.record_substitution(&1);
+        game = game
+            .on_event(Event::EndPeriod)
+            .and_then(|g| g.on_event(Event::StartPeriod))
+            .unwrap_or_else(|_| panic!("next period should start"));
+
+        game.record_substitution(&2);
+        assert_eq!(game.windows_used(), 2);
+    }
+
+    #[test]
+    fn test_rolling_and_half_time_only() {
+        let mut game = in_progress(SubRule::Rolling, 0);
+        game.record_substitution(&1);
+        assert_eq!(game.check_sub_rules(), Ok(()));
+        assert_eq!(game.subs_remaining(), None);
+
+        let game = in_progress(SubRule::HalfTimeOnly, 0);
+        assert!(game.check_sub_rules().is_err());
+    }
+}
@@ -0,0 +1,87 @@
+use super::core::Game;
+use super::data::Team;
+use chrono::{DateTime, Utc};
+
+/// `TimelineEntry` is a notable moment in a game, eg: a period starting or a card being shown.
+#[derive(Clone, Debug)]
+pub struct TimelineEntry {
+    pub time: DateTime<Utc>,
+    pub period: usize, // 1 based, 0 before the game starts.
+    pub description: String,
+}
+
+impl Game {
+    /// `player_label` returns the player's number and name for display, eg: "#7 Sam".
+    pub fn player_label(&self, player_id: &u32) -> String {
+        self.shared
+            .players
+            .iter()
+            .find(|p| &p.id == player_id)
+            .map(|p| format!("#{} {}", p.number, p.name))
+            .unwrap_or_else(|| format!("player {player_id}"))
+    }
+
+    /// `timeline` lists the game's notable moments, oldest first.
+    pub fn timeline(&self) -> Vec<TimelineEntry> {
+        let mut entries = vec![];
+
+        for (i, period) in self.shared.periods.iter().enumerate() {
+            entries.push(TimelineEntry {
+                time: period.start_time,
+                period: i + 1,
+                description: format!("Period {} started", i + 1),
+            });
+            if let Some(end_time) = period.end_time {
+                entries.push(TimelineEntry {
+                    time: end_time,
+                    period: i + 1,
+                    description: format!("Period {} ended", i + 1),
+                });
+            }
+        }
+
+        for foul in &self.shared.fouls {
+            entries.push(TimelineEntry {
+                time: foul.time,
+                period: foul.period,
+                description: format!("Foul {}", self.player_label(&foul.player_id)),
+            });
+        }
+
+        for card in &self.shared.cards {
+            entries.push(TimelineEntry {
+                time: card.time,
+                period: card.period,
+                description: format!("{} card {}", card.kind, self.player_label(&card.player_id)),
+            });
+        }
+
+        for incident in &self.shared.incidents {
+            entries.push(TimelineEntry {
+                time: incident.time,
+                period: incident.period,
+                description: format!(
+                    "Incident {}: {}",
+                    self.player_label(&incident.player_id),
+                    incident.description
+                ),
+            });
+        }
+
+        for score in &self.shared.scores {
+            let scorer = match (score.team, score.player_id) {
+                (Team::Us, Some(player_id)) => self.player_label(&player_id),
+                (Team::Us, None) => "us".to_string(),
+                (Team::Them, _) => "opposition".to_string(),
+            };
+            entries.push(TimelineEntry {
+                time: score.time,
+                period: score.period,
+                description: format!("{} points {}", score.points, scorer),
+            });
+        }
+
+        entries.sort_by_key(|e| e.time);
+        entries
+    }
+}
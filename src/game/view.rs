@@ -1,7 +1,13 @@
 use super::core::Game;
 use super::data::Period;
+use super::data::{Award, CardKind, Shot, Team};
+use super::incidents::IncidentEntry;
+use super::notes::NoteEntry;
 use super::state::GameState;
-use crate::player::{PlayerView, into_player_views};
+use super::timeline::TimelineEntry;
+use crate::player::{PlayerView, Window, duration, into_player_views};
+use crate::settings::Surface;
+use crate::stats::{ShootingTotals, shooting_totals};
 use chrono::{DateTime, TimeDelta, Utc};
 
 const TIME_FORMAT_DIGITAL: &str = "%H:%M:%S";
@@ -11,14 +17,6 @@ fn digital_time(date: Option<DateTime<Utc>>) -> String {
         .unwrap_or_else(|| "-".to_string())
 }
 
-fn duration(delta: TimeDelta) -> String {
-    let total_seconds = delta.num_seconds();
-    let minutes = total_seconds / 60;
-    let seconds = total_seconds % 60;
-
-    format!("{}m {}s", minutes, seconds)
-}
-
 /// `GameView` is a read-only view of a `Game` with useful data provided as struct fields and via
 /// helper methods. It is intended for use in HTML and other presentation layers.
 pub struct GameView {
@@ -30,6 +28,42 @@ pub struct GameView {
     pub periods: Vec<Period>,
     pub players: Vec<PlayerView>,
     pub mvp: Option<u32>,
+    pub lineup_warnings: Vec<String>,
+    pub foul_out_limit: u32,
+    pub team_foul_bonus: u32,
+    /// team fouls in the current, or most recent, period.
+    pub team_fouls: u32,
+    pub on_court: u32,
+    /// on court limit less active suspensions, `None` when unlimited.
+    pub allowed_on_court: Option<u32>,
+    pub season: String,
+    pub timeline: Vec<TimelineEntry>,
+    pub subs_remaining: Option<String>,
+    pub score_us: u32,
+    pub score_them: u32,
+    pub shots: Vec<Shot>,
+    pub surface: Surface,
+    pub shooting: Vec<ShootingTotals>,
+    pub award_categories: Vec<String>,
+    pub awards: Vec<Award>,
+    /// player of the match voting token, `None` until opened.
+    pub voting_token: Option<String>,
+    pub voting_open: bool,
+    /// votes per player id, most votes first.
+    pub vote_tally: Vec<(u32, u32)>,
+    pub incidents: Vec<IncidentEntry>,
+    pub notes: Vec<NoteEntry>,
+    pub opponent: String,
+    pub scheduled_at: Option<DateTime<Utc>>,
+    pub venue: String,
+    pub round: String,
+    /// the team that forfeited, for a forfeited game.
+    pub forfeited_by: Option<Team>,
+    /// minimum participation settings, 0 disables.
+    pub min_periods: u32,
+    pub min_minutes: u32,
+    pub revision: u32,
+    pub updated_at: Option<DateTime<Utc>>,
 }
 
 impl GameView {
@@ -58,6 +92,45 @@ impl GameView {
 
         "-".to_string()
     }
+
+    pub fn award_winner(&self, category: &str) -> Option<u32> {
+        self.awards
+            .iter()
+            .find(|a| a.category == category)
+            .map(|a| a.player_id)
+    }
+
+    /// `player_name` returns the name of the game's player, or "-" if unknown.
+    pub fn player_name(&self, player_id: &u32) -> String {
+        self.players
+            .iter()
+            .find(|p| &p.id == player_id)
+            .map(|p| p.name.clone())
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn in_bonus(&self) -> bool {
+        self.team_foul_bonus > 0 && self.team_fouls >= self.team_foul_bonus
+    }
+
+    /// `fairness` compares the lowest and highest share of available time played, 100% meaning
+    /// every player got an even share. Players that were never available are ignored.
+    pub fn fairness(&self) -> String {
+        let ratios: Vec<f64> = self
+            .players
+            .iter()
+            .filter_map(PlayerView::available_ratio)
+            .collect();
+
+        let min = ratios.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = ratios.iter().copied().fold(0.0, f64::max);
+
+        if max > 0.0 {
+            format!("{:.0}%", min / max * 100.0)
+        } else {
+            "-".to_string()
+        }
+    }
 }
 
 /// `into_game_views` is a helper function to simplify converting a vector of Game's into
@@ -76,21 +149,238 @@ impl From<Game> for GameView {
 // Convert from a borrowed `Game`.
 impl From<&Game> for GameView {
     fn from(game: &Game) -> GameView {
-        let (start_time, end_time) = match &game.state {
-            super::state::State::NotStarted(_) => (None, None),
-            super::state::State::InProgress(p) => (Some(p.state.start_time), None),
-            super::state::State::Paused(p) => (Some(p.state.start_time), None),
-            super::state::State::Finished(p) => (Some(p.state.start_time), Some(p.state.end_time)),
+        let (start_time, end_time) = (game.state.start_time(), game.state.end_time());
+
+        let live = matches!(game.state.kind(), GameState::InProgress | GameState::Paused);
+        let bench_warning = TimeDelta::minutes(game.shared.settings.bench_warning_minutes.into());
+        // only time within periods counts, breaks aren't playable.
+        let in_periods = |(from, until): Window| -> TimeDelta {
+            game.shared
+                .periods
+                .iter()
+                .map(|p| p.overlap(from, until))
+                .sum()
         };
 
+        let mut players = into_player_views(game.shared.players.clone());
+        for (view, player) in players.iter_mut().zip(&game.shared.players) {
+            view.available_duration = player.availability().map_or(TimeDelta::zero(), in_periods);
+
+            let rests: Vec<TimeDelta> = player.rests().into_iter().map(in_periods).collect();
+            view.bench_duration = rests.iter().sum();
+            view.longest_rest = rests.iter().max().copied().unwrap_or_default();
+            view.current_rest = rests
+                .last()
+                .filter(|_| live && player.is_available() && !player.is_playing())
+                .copied();
+            view.rest_warning = bench_warning > TimeDelta::zero()
+                && view.current_rest.is_some_and(|r| r > bench_warning);
+            view.fouls = game.player_fouls(&player.id);
+            view.fouled_out = game.fouled_out(&player.id);
+            // one more and they're out.
+            view.foul_trouble = game.shared.settings.foul_out_limit > 1
+                && view.fouls + 1 == game.shared.settings.foul_out_limit;
+            view.cards = game
+                .shared
+                .cards
+                .iter()
+                .filter(|c| c.player_id == player.id)
+                .map(|c| c.kind)
+                .collect::<Vec<CardKind>>();
+            view.suspension = game.suspended(&player.id).filter(|_| live);
+            view.sent_off = game.sent_off(&player.id);
+            view.points = game.player_points(&player.id);
+            view.plus_minus = game.plus_minus(&player.id);
+        }
+
         Self {
             id: game.id,
             start_time,
             end_time,
             periods: game.shared.periods.clone(),
-            players: into_player_views(game.shared.players.clone()),
+            players,
             state: game.state.kind(),
             mvp: game.shared.mvp,
+            lineup_warnings: game.shared.lineup_warnings.clone(),
+            foul_out_limit: game.shared.settings.foul_out_limit,
+            team_foul_bonus: game.shared.settings.team_foul_bonus,
+            team_fouls: game.team_fouls(game.shared.periods.len()),
+            on_court: game.on_court(),
+            allowed_on_court: game.allowed_on_court(),
+            season: game.shared.settings.season.clone(),
+            timeline: game.timeline(),
+            subs_remaining: game.subs_remaining(),
+            score_us: game.score(Team::Us),
+            score_them: game.score(Team::Them),
+            shots: game.shared.shots.clone(),
+            surface: game.shared.settings.surface,
+            shooting: shooting_totals(std::slice::from_ref(game)),
+            award_categories: game.shared.settings.award_categories.clone(),
+            awards: game.shared.awards.clone(),
+            voting_token: game.shared.voting.as_ref().map(|v| v.token.clone()),
+            voting_open: game.voting_open(),
+            vote_tally: game.vote_tally(),
+            incidents: game.incident_entries(),
+            notes: game.note_entries(),
+            opponent: game.shared.opponent.clone(),
+            scheduled_at: game.shared.scheduled_at,
+            venue: game.shared.venue.clone(),
+            round: game.shared.round.clone(),
+            forfeited_by: game.state.forfeited_by(),
+            min_periods: game.shared.settings.min_periods,
+            min_minutes: game.shared.settings.min_minutes,
+            revision: game.shared.revision,
+            updated_at: game.shared.updated_at,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::player::Stint;
+    use crate::{Player, Settings};
+
+    // live returns an in progress game with `periods` and `players`, times in minutes ago and
+    // `None` for a period still being played.
+    fn live(periods: &[(i64, Option<i64>)], players: Vec<Player>, settings: Settings) -> Game {
+        let mut game = Game::new(1, vec![], settings)
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"));
+        game.shared.periods = periods
+            .iter()
+            .map(|(start, end)| Period {
+                start_time: ago(*start),
+                end_time: end.map(ago),
+            })
+            .collect();
+        game.shared.players = players;
+
+        game
+    }
+
+    fn ago(minutes: i64) -> DateTime<Utc> {
+        Utc::now() - TimeDelta::minutes(minutes)
+    }
+
+    fn player(id: u32, stints: &[(i64, i64)]) -> Player {
+        let mut player = Player::new(id, id, id.to_string());
+        player.stints = stints
+            .iter()
+            .map(|(start, end)| Stint {
+                start_time: ago(*start),
+                end_time: ago(*end),
+            })
+            .collect();
+        player.play_duration = player
+            .stints
+            .iter()
+            .map(|s| s.end_time - s.start_time)
+            .sum();
+
+        player
+    }
+
+    fn about(delta: TimeDelta, minutes: i64) -> bool {
+        let expected = TimeDelta::minutes(minutes);
+        delta >= expected && delta < expected + TimeDelta::seconds(5)
+    }
+
+    #[test]
+    fn test_bench_time_within_periods() {
+        // rested five minutes of the first period, the break and eighteen minutes of the second.
+        let periods = [(40, Some(30)), (20, None)];
+        let game = live(
+            &periods,
+            vec![player(1, &[(40, 35), (20, 18)])],
+            Settings::default(),
+        );
+        let view = GameView::from(&game);
+        let player = &view.players[0];
+
+        assert!(about(player.bench_duration, 23));
+        assert!(about(player.longest_rest, 18));
+        assert!(player.current_rest.is_some_and(|r| about(r, 18)));
+    }
+
+    #[test]
+    fn test_rest_warning() {
+        let periods = [(20, None)];
+        let warning = |minutes| {
+            let settings = Settings {
+                bench_warning_minutes: minutes,
+                ..Settings::default()
+            };
+            let game = live(&periods, vec![player(1, &[(20, 15)])], settings);
+            GameView::from(&game).players[0].rest_warning
+        };
+
+        assert!(warning(14));
+        assert!(!warning(16));
+        assert!(!warning(0));
+    }
+
+    #[test]
+    fn test_no_current_rest() {
+        let periods = [(20, None)];
+        let mut on_court = player(1, &[(20, 15)]);
+        on_court.play_start_time = Some(ago(5));
+        let mut late = player(2, &[]);
+        late.mark_late();
+        let game = live(&periods, vec![on_court, late], Settings::default());
+        let view = GameView::from(&game);
+
+        assert!(about(view.players[0].bench_duration, 10));
+        assert_eq!(view.players[0].current_rest, None);
+        assert!(!view.players[0].rest_warning);
+        assert_eq!(view.players[1].bench_duration, TimeDelta::zero());
+        assert_eq!(view.players[1].current_rest, None);
+
+        // nobody is resting once the game is over.
+        let game = game
+            .on_event(Event::EndGame)
+            .unwrap_or_else(|_| panic!("game should end"));
+        assert_eq!(GameView::from(&game).players[0].current_rest, None);
+    }
+
+    #[test]
+    fn test_fairness() {
+        // over twenty minutes, one player on for ten and another for all twenty.
+        let periods = [(40, Some(30)), (20, Some(10))];
+        let mut arrived = player(3, &[(15, 10)]);
+        arrived.arrived_at = Some(ago(20));
+        let mut late = player(4, &[]);
+        late.mark_late();
+        let game = live(
+            &periods,
+            vec![
+                player(1, &[(40, 30)]),
+                player(2, &[(40, 30), (20, 10)]),
+                late,
+            ],
+            Settings::default(),
+        );
+        let view = GameView::from(&game);
+        assert_eq!(view.players[0].available_percentage(), "50%");
+        assert_eq!(view.players[1].available_percentage(), "100%");
+        assert_eq!(view.players[2].available_percentage(), "-");
+        assert_eq!(view.fairness(), "50%");
+
+        // a late arrival is judged on the time they were there.
+        let game = live(&periods, vec![arrived], Settings::default());
+        let view = GameView::from(&game);
+        assert_eq!(view.players[0].available_percentage(), "50%");
+        assert_eq!(view.fairness(), "100%");
+    }
+
+    #[test]
+    fn test_fairness_nobody_played() {
+        let periods = [(20, Some(10))];
+        let game = live(&periods, vec![player(1, &[])], Settings::default());
+        assert_eq!(GameView::from(&game).fairness(), "-");
+
+        let game = live(&[], vec![player(1, &[])], Settings::default());
+        assert_eq!(GameView::from(&game).fairness(), "-");
+    }
+}
@@ -0,0 +1,81 @@
+use super::core::Game;
+use super::data::{Vote, Voting};
+use chrono::Utc;
+
+impl Game {
+    /// `open_voting` opens player of the match voting, reopening it with the same link if it was
+    /// closed. Returns the voting token.
+    pub fn open_voting(&mut self) -> String {
+        let voting = self.shared.voting.get_or_insert_with(|| Voting {
+            token: uuid::Uuid::new_v4().simple().to_string(),
+            open: true,
+            votes: vec![],
+        });
+        voting.open = true;
+
+        voting.token.clone()
+    }
+
+    pub fn close_voting(&mut self) {
+        if let Some(voting) = &mut self.shared.voting {
+            voting.open = false;
+        }
+    }
+
+    pub fn voting_open(&self) -> bool {
+        self.shared.voting.as_ref().is_some_and(|v| v.open)
+    }
+
+    /// `vote` returns the device's vote to record, replacing any earlier vote from the same
+    /// device. Returns `None` when voting isn't open.
+    pub fn vote(&self, device_id: &str, player_id: &u32) -> Option<Vote> {
+        self.voting_open().then(|| Vote {
+            device_id: device_id.to_string(),
+            player_id: *player_id,
+            time: Utc::now(),
+        })
+    }
+
+    /// `set_votes` replaces the game's votes. Repos store votes apart from the game, so voters
+    /// never rewrite the game while it's being played.
+    pub fn set_votes(&mut self, votes: Vec<Vote>) {
+        if let Some(voting) = &mut self.shared.voting {
+            voting.votes = votes;
+        }
+    }
+
+    /// `take_votes` removes the game's votes, for storing apart from the game.
+    pub fn take_votes(&mut self) -> Vec<Vote> {
+        self.shared
+            .voting
+            .as_mut()
+            .map(|v| std::mem::take(&mut v.votes))
+            .unwrap_or_default()
+    }
+
+    /// `device_vote` returns who the device voted for.
+    pub fn device_vote(&self, device_id: &str) -> Option<u32> {
+        self.shared
+            .voting
+            .as_ref()?
+            .votes
+            .iter()
+            .find(|v| v.device_id == device_id)
+            .map(|v| v.player_id)
+    }
+
+    /// `vote_tally` counts the votes per player, most votes first.
+    pub fn vote_tally(&self) -> Vec<(u32, u32)> {
+        let mut tally: Vec<(u32, u32)> = vec![];
+
+        for vote in self.shared.voting.iter().flat_map(|v| &v.votes) {
+            match tally.iter_mut().find(|(id, _)| id == &vote.player_id) {
+                Some((_, count)) => *count += 1,
+                None => tally.push((vote.player_id, 1)),
+            }
+        }
+
+        tally.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+        tally
+    }
+}
@@ -0,0 +1,208 @@
+use crate::GameView;
+use crate::player::duration;
+use crate::stats::SeasonMinutes;
+use chrono::{DateTime, Utc};
+use maud::{Markup, html};
+
+const ON_COURT_COLOUR: &str = "#1565c0";
+const PERIOD_COLOUR: &str = "#9e9e9e";
+const PLAY_COUNT_COLOUR: &str = "#ef6c00";
+
+// chart layout in SVG user units, the label column is left of the plot.
+const LABEL_WIDTH: f64 = 120.0;
+const PLOT_WIDTH: f64 = 480.0;
+const ROW_HEIGHT: usize = 20;
+const AXIS_HEIGHT: usize = 16;
+const CELL_WIDTH: usize = 28;
+const TREND_WIDTH: f64 = 240.0;
+const TREND_HEIGHT: f64 = 60.0;
+
+/// `playing_time` is a Gantt chart of the game with a row per player and a bar per stint on court,
+/// periods shaded behind with their number above.
+pub fn playing_time(game: &GameView) -> Markup {
+    let (Some(first), Some(last)) = (game.periods.first(), game.periods.last()) else {
+        return html! {};
+    };
+    let now = Utc::now();
+    let start = first.start_time;
+    let span = (last.end_time.unwrap_or(now) - start)
+        .num_milliseconds()
+        .max(1) as f64;
+    let x = |time: DateTime<Utc>| {
+        LABEL_WIDTH + (time - start).num_milliseconds() as f64 / span * PLOT_WIDTH
+    };
+    let height = AXIS_HEIGHT + game.players.len() * ROW_HEIGHT;
+
+    html! {
+        svg
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox={ "0 0 " (LABEL_WIDTH + PLOT_WIDTH) " " (height) }
+            width="100%"
+            style="max-width: 720px"
+        {
+            @for (i, period) in game.periods.iter().enumerate() {
+                @let x1 = x(period.start_time);
+                @let x2 = x(period.end_time.unwrap_or(now));
+                rect
+                    x=(format!("{:.1}", x1))
+                    y="0"
+                    width=(format!("{:.1}", x2 - x1))
+                    height=(height)
+                    fill=(PERIOD_COLOUR)
+                    fill-opacity="0.15" {}
+                line
+                    x1=(format!("{:.1}", x1))
+                    y1="0"
+                    x2=(format!("{:.1}", x1))
+                    y2=(height)
+                    stroke=(PERIOD_COLOUR)
+                    stroke-dasharray="2 2" {}
+                text x=(format!("{:.1}", x1 + 2.0)) y="12" font-size="11" fill="currentColor" {
+                    "P" (i + 1)
+                }
+            }
+            @for (i, player) in game.players.iter().enumerate() {
+                @let y = AXIS_HEIGHT + i * ROW_HEIGHT;
+                text x="0" y=(y + 14) font-size="12" fill="currentColor" {
+                    "#" (player.number) " " (player.name)
+                }
+                @for stint in &player.stints {
+                    (stint_bar(x(stint.start_time), x(stint.end_time), y))
+                }
+                @if let Some(st) = player.play_start_time {
+                    (stint_bar(x(st), x(now), y))
+                }
+            }
+        }
+    }
+}
+
+fn stint_bar(x1: f64, x2: f64, y: usize) -> Markup {
+    html! {
+        rect
+            x=(format!("{:.1}", x1))
+            y=(y + 4)
+            width=(format!("{:.1}", (x2 - x1).max(1.0)))
+            height="12"
+            fill=(ON_COURT_COLOUR) {}
+    }
+}
+
+/// `minutes_heatmap` is a player by game grid, each cell shaded by the minutes played relative to
+/// the most played in a game. Games the player wasn't in are left blank.
+pub fn minutes_heatmap(season: &SeasonMinutes) -> Markup {
+    let max = season.max_played().num_milliseconds().max(1) as f64;
+    let width = LABEL_WIDTH as usize + season.game_ids.len() * CELL_WIDTH;
+    let height = AXIS_HEIGHT + season.players.len() * ROW_HEIGHT;
+
+    html! {
+        svg
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox={ "0 0 " (width) " " (height) }
+            width="100%"
+            style={ "max-width: " (width * 3 / 2) "px" }
+        {
+            @for (i, game_id) in season.game_ids.iter().enumerate() {
+                a href={ "/games/" (game_id) } {
+                    text
+                        x=(LABEL_WIDTH as usize + i * CELL_WIDTH + CELL_WIDTH / 2)
+                        y="12"
+                        font-size="11"
+                        text-anchor="middle"
+                        fill="currentColor"
+                    { (game_id) }
+                }
+            }
+            @for (row, player) in season.players.iter().enumerate() {
+                @let y = AXIS_HEIGHT + row * ROW_HEIGHT;
+                text x="0" y=(y + 14) font-size="12" fill="currentColor" {
+                    "#" (player.number) " " (player.name)
+                }
+                @for (i, played) in player.played.iter().enumerate() {
+                    @if let Some(played) = played {
+                        @let share = played.num_milliseconds() as f64 / max;
+                        rect
+                            x=(LABEL_WIDTH as usize + i * CELL_WIDTH + 1)
+                            y=(y + 1)
+                            width=(CELL_WIDTH - 2)
+                            height=(ROW_HEIGHT - 2)
+                            fill=(ON_COURT_COLOUR)
+                            fill-opacity=(format!("{:.2}", share.max(0.05)))
+                        {
+                            title { "Game " (season.game_ids[i]) ": " (duration(*played)) }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// trend_points plots the values across the chart, skipping games the player wasn't in.
+fn trend_points(values: &[Option<f64>], max: f64) -> String {
+    let step = TREND_WIDTH / values.len().saturating_sub(1).max(1) as f64;
+    values
+        .iter()
+        .enumerate()
+        .filter_map(|(i, v)| {
+            v.map(|v| {
+                let y = TREND_HEIGHT - v / max.max(1.0) * (TREND_HEIGHT - 4.0) - 2.0;
+                format!("{:.1},{:.1}", i as f64 * step, y)
+            })
+        })
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+/// `player_trends` charts each player's minutes and play count per game across the season, both
+/// scaled to the most in a game so players can be compared.
+pub fn player_trends(season: &SeasonMinutes) -> Markup {
+    let max_played = season.max_played().num_milliseconds() as f64;
+    let max_play_count = season.max_play_count() as f64;
+
+    html! {
+        p {
+            span style={ "color: " (ON_COURT_COLOUR) } { "━ minutes" }
+            " "
+            span style={ "color: " (PLAY_COUNT_COLOUR) } { "━ play count" }
+        }
+        div class="grid" {
+            @for player in &season.players {
+                @let played: Vec<Option<f64>> = player
+                    .played
+                    .iter()
+                    .map(|p| p.map(|p| p.num_milliseconds() as f64))
+                    .collect();
+                @let play_counts: Vec<Option<f64>> = player
+                    .play_counts
+                    .iter()
+                    .map(|c| c.map(f64::from))
+                    .collect();
+                div class="s12 m6 l4" {
+                    h6 class="small" {
+                        a href={ "/players/" (player.player_id) } {
+                            "#" (player.number) " " (player.name)
+                        }
+                    }
+                    svg
+                        xmlns="http://www.w3.org/2000/svg"
+                        viewBox={ "-2 0 " (TREND_WIDTH + 4.0) " " (TREND_HEIGHT) }
+                        width="100%"
+                    {
+                        line x1="0" y1=(TREND_HEIGHT) x2=(TREND_WIDTH) y2=(TREND_HEIGHT) stroke=(PERIOD_COLOUR) {}
+                        polyline
+                            points=(trend_points(&played, max_played))
+                            fill="none"
+                            stroke=(ON_COURT_COLOUR)
+                            stroke-width="2" {}
+                        polyline
+                            points=(trend_points(&play_counts, max_play_count))
+                            fill="none"
+                            stroke=(PLAY_COUNT_COLOUR)
+                            stroke-width="2" {}
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,16 +1,28 @@
 //! `http` wraps the domain service and provides http endpoints for interacting with the Service.
 
-use super::{games_templates, layout_templates, players_templates};
-use crate::{Error, GameView, Service, into_game_views, into_player_views};
+use super::{
+    games_templates, ics, layout_templates, pdf, players_templates, report_templates,
+    settings_templates, shots_templates, stats_templates, summary_templates,
+};
+use crate::dump::{Dump, ImportMode};
+use crate::game::{CardKind, Fixture, Team};
+use crate::settings::{Limit, LineupRule, SubRule, Surface};
+use crate::stats::{LeaderboardFilter, LeaderboardSort};
+use crate::{
+    Attribute, Error, Game, GameView, PlayerView, Service, into_game_views, into_player_views,
+};
 use axum::{
     Router,
-    extract::{Form, FromRequestParts, Path, State},
+    extract::{DefaultBodyLimit, FromRequestParts, Path, Query, State},
     http::{StatusCode, header, request::Parts},
     middleware::{self},
     response::{Html, IntoResponse, Redirect, Response},
-    routing::{get, post, put},
+    routing::{delete, get, post, put},
 };
+// supports repeated keys, eg: multiple checkboxes with the same name.
+use axum_extra::extract::Form;
 use base64::prelude::*;
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, Utc};
 use maud::Markup;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -92,11 +104,41 @@ impl AxumApp {
             // game
             .route("/games", get(list_games).post(create_game))
             .route("/games/{game_id}", get(get_game))
+            .route("/games/{game_id}/open", post(open_game))
             .route("/games/{game_id}/start", post(start_game))
+            .route("/games/{game_id}/cancel", post(cancel_game))
+            .route("/games/{game_id}/postpone", post(postpone_game))
+            .route("/games/{game_id}/reschedule", post(reschedule_game))
+            .route("/games/{game_id}/forfeit", post(forfeit_game))
+            .route("/games/{game_id}/abandon", post(abandon_game))
             .route("/games/{game_id}/end", post(end_game))
             .route("/games/{game_id}/start-period", post(start_game_period))
             .route("/games/{game_id}/end-period", post(end_game_period))
             .route("/games/{game_id}/mvp", put(upsert_mvp))
+            .route("/games/{game_id}/fixture", put(update_fixture))
+            .route("/games/{game_id}/summary", get(game_summary))
+            .route("/games/{game_id}/report", get(game_report))
+            .route("/games/{game_id}/report.pdf", get(game_report_pdf))
+            .route("/games/{game_id}/awards", put(upsert_award))
+            .route("/games/{game_id}/notes", post(add_note))
+            .route("/games/{game_id}/notes/{index}", delete(remove_note))
+            .route("/games/{game_id}/incidents", post(record_incident))
+            .route(
+                "/games/{game_id}/incidents/{index}",
+                delete(remove_incident),
+            )
+            .route(
+                "/games/{game_id}/voting",
+                get(get_voting).post(open_voting).delete(close_voting),
+            )
+            .route(
+                "/games/{game_id}/shots",
+                post(record_shot).delete(remove_shot),
+            )
+            .route(
+                "/games/{game_id}/opponent-scores",
+                post(record_opponent_score).delete(remove_opponent_score),
+            )
             .route(
                 "/games/{game_id}/players/{player_id}/sub-on",
                 post(sub_player_on),
@@ -105,17 +147,82 @@ impl AxumApp {
                 "/games/{game_id}/players/{player_id}/sub-off",
                 post(sub_player_off),
             )
+            .route(
+                "/games/{game_id}/players/{player_id}/fouls",
+                post(record_foul).delete(remove_foul),
+            )
+            .route(
+                "/games/{game_id}/players/{player_id}/scores",
+                post(record_score).delete(remove_score),
+            )
+            .route(
+                "/games/{game_id}/players/{player_id}/cards",
+                post(record_card).delete(remove_card),
+            )
+            .route(
+                "/games/{game_id}/players/{player_id}/late",
+                post(mark_player_late),
+            )
+            .route(
+                "/games/{game_id}/players/{player_id}/arrive",
+                post(player_arrived),
+            )
+            .route(
+                "/games/{game_id}/players/{player_id}/depart",
+                post(player_departed),
+            )
             // players
             .route("/players", get(list_players).post(create_player))
+            .route("/players/rebuild-totals", post(rebuild_player_totals))
+            .route("/players/import/preview", post(preview_roster))
+            .route("/players/import", post(import_roster))
             .route(
                 "/players/{player_id}",
                 get(get_player).put(edit_player).delete(delete_player),
             )
             .route("/players/{player_id}/edit", get(edit_player_form))
+            // stats
+            .route("/stats", get(leaderboard))
+            .route("/awards", get(awards))
+            .route("/plus-minus", get(plus_minus))
+            .route("/shots", get(shots))
+            .route("/discipline", get(discipline))
+            .route("/incidents", get(incidents))
+            .route("/reports", get(season_report))
+            .route("/reports/season.pdf", get(season_report_pdf))
+            // exports
+            .route("/export/players.csv", get(export_players))
+            .route("/export/games.csv", get(export_games))
+            .route(
+                "/export/games/{game_id}/minutes.csv",
+                get(export_game_minutes),
+            )
+            .route("/export/dump.json", get(export_dump))
+            .route(
+                "/import/dump",
+                post(import_dump).layer(DefaultBodyLimit::max(DUMP_BODY_LIMIT)),
+            )
+            // settings
+            .route("/settings", get(get_settings).put(update_settings))
+            .route("/settings/lineup-rules", post(create_lineup_rule))
+            .route("/settings/lineup-rules/{index}", delete(delete_lineup_rule))
+            .route("/settings/award-categories", post(create_award_category))
+            .route(
+                "/settings/award-categories/{index}",
+                delete(delete_award_category),
+            )
+            .route(
+                "/settings/calendar",
+                post(reset_calendar_token).delete(disable_calendar),
+            )
             // basic auth required for above route(s)
             .route_layer(middleware::from_extractor_with_state::<RequireAuth, _>(
                 auth_config.clone(),
             ))
+            // parents and spectators vote without logging in, the token being the secret.
+            .route("/vote/{token}", get(get_vote).post(cast_vote))
+            // calendar apps can't log in, the token query parameter being the secret.
+            .route("/calendar.ics", get(calendar))
             // state (db, etc)
             .with_state(state.clone())
             // basic auth not required for below routes
@@ -132,6 +239,10 @@ struct AppState {
 struct NewPlayerForm {
     pub name: String,
     pub number: u32,
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    #[serde(default)]
+    pub position: String,
 }
 
 #[derive(Clone)]
@@ -197,38 +308,65 @@ async fn home(State(state): State<AppState>) -> Result<impl IntoResponse, Error>
     let description = "Manage your sports game subs";
 
     let games = into_game_views(state.svc.list_games()?);
-    let players = into_player_views(state.svc.list_players()?);
 
     let games_html = games_templates::list_games(&games);
-    let players_html = players_templates::list_players(&players);
+    let players_html = list_players_html(&state.svc)?;
     let contents = layout_templates::games_players(&games_html, &players_html);
     let body = Html(layout_templates::page(title, description, &contents).into_string());
 
     Ok((StatusCode::OK, body))
 }
 
+#[derive(Debug, Deserialize)]
+struct PlayerQuery {
+    pub season: Option<String>,
+    pub tag: Option<String>,
+}
+
 async fn get_player(
     State(state): State<AppState>,
     Path(player_id): Path<String>,
+    Query(query): Query<PlayerQuery>,
+    headers: header::HeaderMap,
 ) -> Result<impl IntoResponse, Error> {
     let player_id: u32 = player_id
         .trim()
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
 
-    let player = state.svc.get_player(&player_id)?;
-    let body = Html(players_templates::player_table_row(&player.into()).into_string());
+    let player: PlayerView = state.svc.get_player(&player_id)?.into();
 
-    Ok((StatusCode::OK, body))
+    if headers.contains_key("HX-Request") {
+        // row will be swapped into the players table.
+        let body = players_templates::player_table_row(&player).into_string();
+        return Ok((StatusCode::OK, Html(body)));
+    }
+
+    let seasons = state.svc.list_seasons()?;
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+    let tag = query.tag.as_deref();
+
+    let totals = state.svc.player_season(&season, &player_id)?;
+    let notes = state.svc.player_notes(&season, &player_id, tag)?;
+    let contents = players_templates::player_page(&player, &season, &seasons, &totals, &notes, tag);
+    let title = format!("#{} {}", player.number, player.name);
+    let body = layout_templates::page(&title, &title, &contents).into_string();
+
+    Ok((StatusCode::OK, Html(body)))
 }
 
 async fn create_player(
     State(state): State<AppState>,
     Form(input): Form<NewPlayerForm>,
 ) -> Result<impl IntoResponse, Error> {
-    let player = state.svc.create_player(input.number, input.name)?;
+    let mut player = state.svc.create_player(input.number, input.name)?;
+    if !input.attributes.is_empty() || !input.position.trim().is_empty() {
+        player.attributes = input.attributes;
+        player.position = input.position.trim().to_string();
+        player = state.svc.update_player(player)?;
+    }
 
-    let body = Html(players_templates::player_table_row(&player.clone().into()).into_string());
+    let body = Html(players_templates::player_table_row(&player.into()).into_string());
 
     Ok((StatusCode::CREATED, body))
 }
@@ -261,6 +399,8 @@ async fn edit_player(
     let mut player = state.svc.get_player(&player_id)?;
     player.name = input.name;
     player.number = input.number;
+    player.attributes = input.attributes;
+    player.position = input.position.trim().to_string();
     state.svc.update_player(player.clone())?;
 
     let body = Html(players_templates::player_table_row(&player.into()).into_string());
@@ -282,9 +422,62 @@ async fn delete_player(
     Ok(Redirect::to("/players"))
 }
 
-async fn list_players(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+// rebuild_player_totals returns the players table rows with their rebuilt totals.
+async fn rebuild_player_totals(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    let players = into_player_views(state.svc.rebuild_player_totals()?);
+    let body = players
+        .iter()
+        .map(|p| players_templates::player_table_row(p).into_string())
+        .collect::<String>();
+
+    Ok((StatusCode::OK, Html(body)))
+}
+
+#[derive(Debug, Deserialize)]
+struct RosterForm {
+    pub csv: String,
+}
+
+async fn preview_roster(
+    State(state): State<AppState>,
+    Form(input): Form<RosterForm>,
+) -> Result<impl IntoResponse, Error> {
+    let previews = state.svc.preview_roster(&input.csv)?;
+    let body = players_templates::roster_import_preview(&input.csv, &previews);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn import_roster(
+    State(state): State<AppState>,
+    Form(input): Form<RosterForm>,
+) -> Result<impl IntoResponse, Error> {
+    let imported = state.svc.import_roster(&input.csv)?;
     let players = into_player_views(state.svc.list_players()?);
-    let body = Html(players_templates::list_players(&players).into_string());
+    let mut body = players
+        .iter()
+        .map(|p| players_templates::player_table_row(p).into_string())
+        .collect::<String>();
+    body.push_str(&players_templates::roster_imported(imported.len()).into_string());
+
+    Ok((StatusCode::OK, Html(body)))
+}
+
+// list_players_html returns the players with the current season's plus/minus.
+fn list_players_html(svc: &Service) -> Result<Markup, Error> {
+    let players = into_player_views(svc.list_players()?);
+    let season = svc.get_settings()?.season;
+    let plus_minus = svc.plus_minus_totals(&season)?;
+
+    Ok(players_templates::list_players(
+        &players,
+        &season,
+        &plus_minus,
+    ))
+}
+
+async fn list_players(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    let body = Html(list_players_html(&state.svc)?.into_string());
 
     Ok((StatusCode::OK, body))
 }
@@ -316,13 +509,31 @@ async fn assets(Path(filename): Path<String>) -> Result<impl IntoResponse, Error
     Ok(([(header::CONTENT_TYPE, content_type)], body))
 }
 
-async fn create_game(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
-    let game: GameView = state.svc.create_game()?.into();
-    let body = Html(games_templates::game_table_row(&game).into_string());
+// the fixture fields are optional, a game to play straight away has none.
+async fn create_game(
+    State(state): State<AppState>,
+    Form(input): Form<GameFixtureForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game: GameView = state.svc.create_game(input.into_fixture()?)?.into();
+    let body = Html(games_templates::fixture_table_row(&game).into_string());
 
     Ok((StatusCode::CREATED, body))
 }
 
+async fn open_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    state.svc.open_game(&game_id)?;
+
+    Ok(Redirect::to(&format!("/games/{}", game_id)))
+}
+
 async fn list_games(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
     let games = into_game_views(state.svc.list_games()?);
     let body = Html(games_templates::list_games(&games).into_string());
@@ -330,9 +541,12 @@ async fn list_games(State(state): State<AppState>) -> Result<impl IntoResponse,
     Ok((StatusCode::OK, body))
 }
 
-fn get_game_html(game: GameView) -> Markup {
+fn get_game_html(game: Game) -> Markup {
+    let lineups = game.lineups();
+    let game: GameView = game.into();
     let player_actions = players_templates::player_actions(&game.id, &game.state, &game.players);
-    games_templates::get_game(&game, player_actions)
+    let shots = shots_templates::shot_chart(&game);
+    games_templates::get_game(&game, &lineups, player_actions, shots)
 }
 
 async fn get_game(
@@ -345,7 +559,7 @@ async fn get_game(
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.get_game(&game_id)?.into();
+    let game = state.svc.get_game(&game_id)?;
     let contents = get_game_html(game);
 
     let body = if headers.contains_key("HX-Request") {
@@ -370,7 +584,7 @@ async fn start_game(
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.start_game(&game_id)?.into();
+    let game = state.svc.start_game(&game_id)?;
     let body = get_game_html(game).into_string();
 
     Ok((StatusCode::OK, body))
@@ -385,7 +599,88 @@ async fn end_game(
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.end_game(&game_id)?.into();
+    let game = state.svc.end_game(&game_id)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn cancel_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
+
+    let game = state.svc.cancel_game(&game_id)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn postpone_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
+
+    let game = state.svc.postpone_game(&game_id)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn reschedule_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
+
+    let game = state.svc.reschedule_game(&game_id)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct GameForfeitForm {
+    pub team: Team,
+}
+
+async fn forfeit_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<GameForfeitForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
+
+    let game = state.svc.forfeit_game(&game_id, input.team)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn abandon_game(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
+
+    let game = state.svc.abandon_game(&game_id)?;
     let body = get_game_html(game).into_string();
 
     Ok((StatusCode::OK, body))
@@ -400,7 +695,7 @@ async fn start_game_period(
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.start_game_period(&game_id)?.into();
+    let game = state.svc.start_game_period(&game_id)?;
     let body = get_game_html(game).into_string();
 
     Ok((StatusCode::OK, body))
@@ -415,7 +710,7 @@ async fn end_game_period(
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.end_game_period(&game_id)?.into();
+    let game = state.svc.end_game_period(&game_id)?;
     let body = get_game_html(game).into_string();
 
     Ok((StatusCode::OK, body))
@@ -436,105 +731,1066 @@ async fn upsert_mvp(
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.upsert_mvp(&game_id, &input.player_id)?.into();
+    let game = state.svc.upsert_mvp(&game_id, &input.player_id)?;
     let body = get_game_html(game).into_string();
 
     Ok((StatusCode::OK, body))
 }
 
-async fn sub_player_on(
+async fn game_summary(
     State(state): State<AppState>,
-    Path((game_id, player_id)): Path<(String, String)>,
+    Path(game_id): Path<String>,
 ) -> Result<impl IntoResponse, Error> {
     let game_id: u32 = game_id
         .trim()
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
 
-    let player_id: u32 = player_id
-        .trim()
-        .parse::<u32>()
-        .map_err(|_| Error::InvalidInput("player id must be a number".to_string()))?;
-
-    let game: GameView = state.svc.sub_player_on(&game_id, &player_id)?.into();
-    let player = game
-        .players
-        .iter()
-        .find(|p| p.id == player_id)
-        .ok_or_else(|| Error::Internal("player not found".to_string()))?;
+    let game: GameView = state.svc.get_game(&game_id)?.into();
+    if !game.state.has_ended() {
+        return Err(Error::InvalidInput("game has not finished".to_string()));
+    }
 
-    let body = Html(
-        players_templates::player_actions_table_row(&game.id, &game.state, player).into_string(),
-    );
+    let contents = summary_templates::summary(&game);
+    let title = format!("Game {} Summary", game_id);
+    let body = layout_templates::page(&title, &title, &contents);
 
-    Ok((StatusCode::OK, body))
+    Ok((StatusCode::OK, Html(body.into_string())))
 }
 
-async fn sub_player_off(
+async fn game_report(
     State(state): State<AppState>,
-    Path((game_id, player_id)): Path<(String, String)>,
+    Path(game_id): Path<String>,
 ) -> Result<impl IntoResponse, Error> {
     let game_id: u32 = game_id
         .trim()
         .parse::<u32>()
         .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
 
-    let player_id: u32 = player_id
+    let game = state.svc.get_game(&game_id)?;
+    let participation = game.participation();
+    let contents = report_templates::game_report(&game.into(), &participation);
+    let body = layout_templates::print_page(&format!("Game {} Report", game_id), &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn game_report_pdf(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
         .trim()
         .parse::<u32>()
-        .map_err(|_| Error::InvalidInput("player id must be a number".to_string()))?;
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
 
-    let game: GameView = state.svc.sub_player_off(&game_id, &player_id)?.into();
-    let player = game
-        .players
-        .iter()
-        .find(|p| p.id == player_id)
-        .ok_or_else(|| Error::Internal("player not found".to_string()))?;
+    let game = state.svc.get_game(&game_id)?;
+    let participation = game.participation();
+    let game: GameView = game.into();
+    let lines = report_templates::game_report_text(&game, &participation);
+    let body = pdf::document(&summary_templates::title(&game), &lines);
 
-    let body = Html(
-        players_templates::player_actions_table_row(&game.id, &game.state, player).into_string(),
-    );
+    Ok(pdf_response(&format!("game-{}.pdf", game_id), body))
+}
+
+// pdf_response downloads the PDF `body` as `filename`.
+fn pdf_response(filename: &str, body: Vec<u8>) -> impl IntoResponse + use<> {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/pdf".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+}
+
+#[derive(Debug, Deserialize)]
+struct GameFixtureForm {
+    #[serde(default)]
+    pub opponent: String,
+    /// `datetime-local` input, in the server's timezone.
+    #[serde(default)]
+    pub scheduled_at: String,
+    #[serde(default)]
+    pub venue: String,
+    #[serde(default)]
+    pub round: String,
+}
+
+impl GameFixtureForm {
+    fn into_fixture(self) -> Result<Fixture, Error> {
+        Ok(Fixture {
+            scheduled_at: parse_datetime(&self.scheduled_at)?,
+            opponent: self.opponent,
+            venue: self.venue,
+            round: self.round,
+        })
+    }
+}
+
+async fn update_fixture(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<GameFixtureForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+    let game = state.svc.update_fixture(&game_id, input.into_fixture()?)?;
+    let body = get_game_html(game).into_string();
 
     Ok((StatusCode::OK, body))
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{AxumApp, Config, InMemoryRepo, Service};
-    use std::sync::Arc;
+#[derive(Debug, Deserialize)]
+struct GameAwardForm {
+    pub category: String,
+    pub player_id: String, // "-" clears the award.
+}
 
-    use axum::{
-        body::Body,
-        http::{self, Request, StatusCode},
+async fn upsert_award(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<GameAwardForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let player_id = match input.player_id.trim() {
+        "-" => None,
+        id => Some(
+            id.parse::<u32>()
+                .map_err(|_| Error::InvalidInput("player id must be a number".to_string()))?,
+        ),
     };
-    use http_body_util::BodyExt;
-    use tower::ServiceExt; // for `call`, `oneshot`, `ready`, and `collect`
 
-    #[tokio::test]
-    async fn test_get() {
-        let cfg = Config::default();
-        let repo = Arc::new(InMemoryRepo::new());
-        let svc = Service::new(repo);
-        let app = AxumApp::new(cfg.listen_addr, None, svc).into_router();
+    let game = state
+        .svc
+        .upsert_award(&game_id, &input.category, player_id)?;
+    let body = get_game_html(game).into_string();
 
-        let response = app
-            .oneshot(
-                Request::builder()
-                    .uri("/players/1")
-                    .body(Body::empty())
-                    .unwrap(),
-            )
-            .await
-            .unwrap();
+    Ok((StatusCode::OK, body))
+}
 
-        assert_eq!(response.status(), StatusCode::NOT_FOUND);
-    }
+async fn get_voting(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
 
-    #[tokio::test]
-    async fn test_post() {
-        let cfg = Config::default();
-        let repo = Arc::new(InMemoryRepo::new());
-        let svc = Service::new(repo);
+    let game: GameView = state.svc.get_game(&game_id)?.into();
+    let body = Html(games_templates::voting(&game).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn open_voting(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game: GameView = state.svc.open_voting(&game_id)?.into();
+    let body = Html(games_templates::voting(&game).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn close_voting(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game: GameView = state.svc.close_voting(&game_id)?.into();
+    let body = Html(games_templates::voting(&game).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+// DEVICE_COOKIE identifies a voter's device so each device gets a single vote without logging in.
+const DEVICE_COOKIE: &str = "subbers_device";
+
+// device_id returns the voter's device id from the request cookies, if set.
+fn device_id(headers: &header::HeaderMap) -> Option<String> {
+    headers
+        .get_all(header::COOKIE)
+        .iter()
+        .filter_map(|hv| hv.to_str().ok())
+        .flat_map(|hv| hv.split(';'))
+        .filter_map(|c| c.trim().split_once('='))
+        .find(|(name, _)| *name == DEVICE_COOKIE)
+        .map(|(_, value)| value.to_string())
+        .filter(|value| !value.is_empty())
+}
+
+async fn get_vote(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    headers: header::HeaderMap,
+) -> Result<impl IntoResponse, Error> {
+    let game = state.svc.get_voting_game(&token)?;
+    let current_vote = device_id(&headers).and_then(|id| game.device_vote(&id));
+
+    let contents = games_templates::vote(&game.into(), &token, current_vote);
+    let body = layout_templates::public_page("Vote", "Vote for player of the match", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+#[derive(Debug, Deserialize)]
+struct VoteForm {
+    pub player_id: u32,
+}
+
+async fn cast_vote(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+    headers: header::HeaderMap,
+    Form(input): Form<VoteForm>,
+) -> Result<impl IntoResponse, Error> {
+    let (device_id, cookie) = match device_id(&headers) {
+        Some(id) => (id, None),
+        None => {
+            let id = uuid::Uuid::new_v4().simple().to_string();
+            let cookie = format!(
+                "{DEVICE_COOKIE}={id}; Path=/vote; Max-Age=31536000; HttpOnly; SameSite=Lax"
+            );
+            (id, Some(cookie))
+        }
+    };
+
+    let game = state.svc.cast_vote(&token, &device_id, &input.player_id)?;
+    let current_vote = game.device_vote(&device_id);
+    let body = Html(games_templates::vote(&game.into(), &token, current_vote).into_string());
+
+    let mut response = (StatusCode::OK, body).into_response();
+    if let Some(cookie) = cookie {
+        let value =
+            header::HeaderValue::from_str(&cookie).map_err(|e| Error::Internal(e.to_string()))?;
+        response.headers_mut().insert(header::SET_COOKIE, value);
+    }
+
+    Ok(response)
+}
+
+#[derive(Debug, Deserialize)]
+struct IncidentForm {
+    pub player_id: u32,
+    pub description: String,
+}
+
+async fn record_incident(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<IncidentForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game = state
+        .svc
+        .record_incident(&game_id, &input.player_id, &input.description)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::CREATED, Html(body)))
+}
+
+async fn remove_incident(
+    State(state): State<AppState>,
+    Path((game_id, index)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+    let index: usize = index
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| Error::InvalidInput("index must be a number".to_string()))?;
+
+    let game = state.svc.remove_incident(&game_id, index)?;
+    let body = get_game_html(game).into_string();
+
+    Ok((StatusCode::OK, Html(body)))
+}
+
+#[derive(Debug, Deserialize)]
+struct NoteForm {
+    pub player_id: String, // "-" for a note about the game.
+    pub text: String,
+    #[serde(default)]
+    pub tags: String,
+}
+
+async fn add_note(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<NoteForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let player_id = match input.player_id.trim() {
+        "-" => None,
+        id => Some(
+            id.parse::<u32>()
+                .map_err(|_| Error::InvalidInput("player id must be a number".to_string()))?,
+        ),
+    };
+
+    let game: GameView = state
+        .svc
+        .add_note(&game_id, player_id, &input.text, &input.tags)?
+        .into();
+    let body = Html(games_templates::notes(&game).into_string());
+
+    Ok((StatusCode::CREATED, body))
+}
+
+async fn remove_note(
+    State(state): State<AppState>,
+    Path((game_id, index)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+    let index: usize = index
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| Error::InvalidInput("index must be a number".to_string()))?;
+
+    let game: GameView = state.svc.remove_note(&game_id, index)?.into();
+    let body = Html(games_templates::notes(&game).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+// parse_game_player_ids parses the `{game_id}` and `{player_id}` path parameters.
+fn parse_game_player_ids(game_id: String, player_id: String) -> Result<(u32, u32), Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let player_id: u32 = player_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("player id must be a number".to_string()))?;
+
+    Ok((game_id, player_id))
+}
+
+// game_player_row_html renders the player's actions table row, plus out of band updates for the
+// rest of the game page affected by a player action.
+fn game_player_row_html(game: &GameView, player_id: &u32) -> Result<Html<String>, Error> {
+    let player = game
+        .players
+        .iter()
+        .find(|p| &p.id == player_id)
+        .ok_or_else(|| Error::Internal("player not found".to_string()))?;
+
+    Ok(Html(
+        [
+            players_templates::player_actions_table_row(&game.id, &game.state, player),
+            games_templates::lineup_warnings(&game.lineup_warnings, true),
+            games_templates::game_status(game, true),
+            games_templates::game_score(game, true),
+        ]
+        .map(Markup::into_string)
+        .concat(),
+    ))
+}
+
+async fn sub_player_on(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.sub_player_on(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn sub_player_off(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.sub_player_off(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn record_foul(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.record_foul(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn remove_foul(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.remove_foul(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct ScoreForm {
+    pub points: u32,
+}
+
+async fn record_score(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+    Form(input): Form<ScoreForm>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state
+        .svc
+        .record_score(&game_id, &player_id, input.points)?
+        .into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn remove_score(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.remove_score(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn record_opponent_score(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<ScoreForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game: GameView = state
+        .svc
+        .record_opponent_score(&game_id, input.points)?
+        .into();
+    let body = Html(games_templates::game_score(&game, false).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn remove_opponent_score(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game: GameView = state.svc.remove_opponent_score(&game_id)?.into();
+    let body = Html(games_templates::game_score(&game, false).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct ShotForm {
+    pub player_id: u32,
+    pub x: u32,
+    pub y: u32,
+    pub made: bool,
+}
+
+async fn record_shot(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+    Form(input): Form<ShotForm>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game: GameView = state
+        .svc
+        .record_shot(&game_id, &input.player_id, (input.x, input.y), input.made)?
+        .into();
+    let body = Html(shots_templates::shot_chart(&game).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn remove_shot(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let game: GameView = state.svc.remove_shot(&game_id)?.into();
+    let body = Html(shots_templates::shot_chart(&game).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct CardForm {
+    pub kind: CardKind,
+}
+
+async fn record_card(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+    Form(input): Form<CardForm>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state
+        .svc
+        .record_card(&game_id, &player_id, input.kind)?
+        .into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn remove_card(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.remove_card(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn mark_player_late(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.mark_player_late(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn player_arrived(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.player_arrived(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn player_departed(
+    State(state): State<AppState>,
+    Path((game_id, player_id)): Path<(String, String)>,
+) -> Result<impl IntoResponse, Error> {
+    let (game_id, player_id) = parse_game_player_ids(game_id, player_id)?;
+
+    let game: GameView = state.svc.player_departed(&game_id, &player_id)?.into();
+    let body = game_player_row_html(&game, &player_id)?;
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct SeasonQuery {
+    pub season: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StatsQuery {
+    pub season: Option<String>,
+    // dates are "YYYY-MM-DD", empty when the filter form field is left blank.
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub opponent: Option<String>,
+    pub sort: Option<LeaderboardSort>,
+}
+
+fn parse_date(date: Option<String>) -> Result<Option<NaiveDate>, Error> {
+    match date.as_deref().map(str::trim) {
+        None | Some("") => Ok(None),
+        Some(d) => NaiveDate::parse_from_str(d, "%Y-%m-%d")
+            .map(Some)
+            .map_err(|_| Error::InvalidInput("dates must be YYYY-MM-DD".to_string())),
+    }
+}
+
+fn parse_datetime(datetime: &str) -> Result<Option<DateTime<Utc>>, Error> {
+    match datetime.trim() {
+        "" => Ok(None),
+        d => NaiveDateTime::parse_from_str(d, "%Y-%m-%dT%H:%M")
+            .ok()
+            .and_then(|d| d.and_local_timezone(Local).earliest())
+            .map(|d| Some(d.with_timezone(&Utc)))
+            .ok_or_else(|| Error::InvalidInput("times must be YYYY-MM-DDTHH:MM".to_string())),
+    }
+}
+
+async fn leaderboard(
+    State(state): State<AppState>,
+    Query(query): Query<StatsQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let filter = LeaderboardFilter {
+        season: query.season.unwrap_or(state.svc.get_settings()?.season),
+        from: parse_date(query.from)?,
+        to: parse_date(query.to)?,
+        opponent: query.opponent.filter(|o| !o.is_empty()),
+    };
+    let sort = query.sort.unwrap_or_default();
+
+    let rows = state.svc.leaderboard(&filter, sort)?;
+    let opponents = state.svc.list_opponents(&filter.season)?;
+    let minutes = state.svc.season_minutes(&filter)?;
+    let contents =
+        stats_templates::leaderboard(&filter, sort, &seasons, &opponents, &rows, &minutes);
+    let body = layout_templates::page("Stats", "Season leaderboards", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn discipline(
+    State(state): State<AppState>,
+    Query(query): Query<SeasonQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+
+    let totals = state.svc.discipline_totals(&season)?;
+    let contents = stats_templates::discipline(&season, &seasons, &totals);
+    let body = layout_templates::page("Discipline", "Season discipline totals", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn plus_minus(
+    State(state): State<AppState>,
+    Query(query): Query<SeasonQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+
+    let totals = state.svc.plus_minus_totals(&season)?;
+    let lineups = state.svc.season_lineups(&season)?;
+    let contents = stats_templates::plus_minus(&season, &seasons, &totals, &lineups);
+    let body = layout_templates::page("Plus/Minus", "Season plus/minus and lineups", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+#[derive(Debug, Deserialize)]
+struct ShotsQuery {
+    pub season: Option<String>,
+    pub player_id: Option<u32>,
+}
+
+async fn shots(
+    State(state): State<AppState>,
+    Query(query): Query<ShotsQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let settings = state.svc.get_settings()?;
+    let season = query.season.unwrap_or(settings.season);
+
+    let shots = state
+        .svc
+        .season_shots(&season, settings.surface, query.player_id)?;
+    let totals = state.svc.shooting_totals(&season)?;
+    let contents = shots_templates::season_shots(
+        &season,
+        &seasons,
+        settings.surface,
+        query.player_id,
+        &shots,
+        &totals,
+    );
+    let body = layout_templates::page("Shots", "Season shot charts", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn awards(
+    State(state): State<AppState>,
+    Query(query): Query<SeasonQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+
+    let (categories, tallies) = state.svc.award_tallies(&season)?;
+    let contents = stats_templates::awards(&season, &seasons, &categories, &tallies);
+    let body = layout_templates::page("Awards", "Season award tallies", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn incidents(
+    State(state): State<AppState>,
+    Query(query): Query<SeasonQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+
+    let incidents = state.svc.incidents(&season)?;
+    let contents = stats_templates::incidents(&season, &seasons, &incidents);
+    let body = layout_templates::page("Incidents", "Season incidents report", &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn season_report(
+    State(state): State<AppState>,
+    Query(query): Query<SeasonQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let seasons = state.svc.list_seasons()?;
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+
+    let games = into_game_views(state.svc.season_finished_games(&season)?);
+    let totals = state.svc.season_participation(&season)?;
+    let contents = report_templates::season_report(&season, &seasons, &games, &totals);
+    let body = layout_templates::print_page(&format!("Season {} Report", season), &contents);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn season_report_pdf(
+    State(state): State<AppState>,
+    Query(query): Query<SeasonQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let season = query.season.unwrap_or(state.svc.get_settings()?.season);
+
+    let games = into_game_views(state.svc.season_finished_games(&season)?);
+    let totals = state.svc.season_participation(&season)?;
+    let lines = report_templates::season_report_text(&games, &totals);
+    let body = pdf::document(&format!("Season {} Report", season), &lines);
+    // the season label is free text, keep the filename simple.
+    let filename: String = season
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+
+    Ok(pdf_response(&format!("season-{}.pdf", filename), body))
+}
+
+// csv_response downloads the CSV `body` as `filename`.
+fn csv_response(filename: &str, body: String) -> impl IntoResponse + use<> {
+    (
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+}
+
+async fn export_players(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    Ok(csv_response("players.csv", state.svc.export_players_csv()?))
+}
+
+async fn export_games(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    Ok(csv_response("games.csv", state.svc.export_games_csv()?))
+}
+
+async fn export_game_minutes(
+    State(state): State<AppState>,
+    Path(game_id): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let game_id: u32 = game_id
+        .trim()
+        .parse::<u32>()
+        .map_err(|_| Error::InvalidInput("game id must be a number".to_string()))?;
+
+    let body = state.svc.export_game_minutes_csv(&game_id)?;
+
+    Ok(csv_response(&format!("game-{}-minutes.csv", game_id), body))
+}
+
+async fn export_dump(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    let dump = state.svc.export_dump()?;
+    let filename = format!("subbers-{}.json", dump.exported_at.format("%Y-%m-%d"));
+
+    Ok((
+        StatusCode::OK,
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        dump.to_json()?,
+    ))
+}
+
+// DUMP_BODY_LIMIT allows importing dumps larger than axum's default 2MB request body limit.
+const DUMP_BODY_LIMIT: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, Deserialize)]
+struct DumpForm {
+    pub json: String,
+    pub mode: ImportMode,
+}
+
+async fn import_dump(
+    State(state): State<AppState>,
+    Form(input): Form<DumpForm>,
+) -> Result<impl IntoResponse, Error> {
+    let summary = state
+        .svc
+        .import_dump(Dump::parse(&input.json)?, input.mode)?;
+    let body = settings_templates::dump_imported(&summary);
+
+    Ok((StatusCode::OK, Html(body.into_string())))
+}
+
+async fn get_settings(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    let settings = state.svc.get_settings()?;
+    let calendar_token = state.svc.calendar_token()?;
+    let contents = settings_templates::get_settings(&settings, &calendar_token);
+    let body = layout_templates::page("Settings", "Team settings", &contents).into_string();
+
+    Ok((StatusCode::OK, Html(body)))
+}
+
+#[derive(Debug, Deserialize)]
+struct SettingsForm {
+    pub bench_warning_minutes: u32,
+    pub foul_out_limit: u32,
+    pub team_foul_bonus: u32,
+    pub on_court_limit: u32,
+    pub green_card_minutes: u32,
+    pub yellow_card_minutes: u32,
+    pub season: String,
+    pub sub_rule: SubRule,
+    pub sub_limit: u32,
+    pub surface: Surface,
+    pub min_periods: u32,
+    pub min_minutes: u32,
+}
+
+async fn update_settings(
+    State(state): State<AppState>,
+    Form(input): Form<SettingsForm>,
+) -> Result<impl IntoResponse, Error> {
+    let mut settings = state.svc.get_settings()?;
+    settings.bench_warning_minutes = input.bench_warning_minutes;
+    settings.foul_out_limit = input.foul_out_limit;
+    settings.team_foul_bonus = input.team_foul_bonus;
+    settings.on_court_limit = input.on_court_limit;
+    settings.green_card_minutes = input.green_card_minutes;
+    settings.yellow_card_minutes = input.yellow_card_minutes;
+    settings.season = input.season.trim().to_string();
+    settings.sub_rule = input.sub_rule;
+    settings.sub_limit = input.sub_limit;
+    settings.surface = input.surface;
+    settings.min_periods = input.min_periods;
+    settings.min_minutes = input.min_minutes;
+
+    let settings = state.svc.update_settings(settings)?;
+    let body = Html(settings_templates::settings_form(&settings).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn reset_calendar_token(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    let token = state.svc.reset_calendar_token()?;
+    let body = Html(settings_templates::calendar(&token).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+async fn disable_calendar(State(state): State<AppState>) -> Result<impl IntoResponse, Error> {
+    let token = state.svc.disable_calendar()?;
+    let body = Html(settings_templates::calendar(&token).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct CalendarQuery {
+    #[serde(default)]
+    pub token: String,
+}
+
+async fn calendar(
+    State(state): State<AppState>,
+    Query(query): Query<CalendarQuery>,
+) -> Result<impl IntoResponse, Error> {
+    let games = into_game_views(state.svc.calendar_games(&query.token)?);
+
+    Ok((
+        StatusCode::OK,
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        ics::calendar("subbers", &games),
+    ))
+}
+
+#[derive(Debug, Deserialize)]
+struct LineupRuleForm {
+    pub attribute: Attribute,
+    pub limit: Limit,
+    pub count: u32,
+}
+
+async fn create_lineup_rule(
+    State(state): State<AppState>,
+    Form(input): Form<LineupRuleForm>,
+) -> Result<impl IntoResponse, Error> {
+    let settings = state.svc.add_lineup_rule(LineupRule {
+        attribute: input.attribute,
+        limit: input.limit,
+        count: input.count,
+    })?;
+    let body = Html(settings_templates::lineup_rules(&settings.lineup_rules).into_string());
+
+    Ok((StatusCode::CREATED, body))
+}
+
+async fn delete_lineup_rule(
+    State(state): State<AppState>,
+    Path(index): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let index: usize = index
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| Error::InvalidInput("index must be a number".to_string()))?;
+
+    let settings = state.svc.delete_lineup_rule(index)?;
+    let body = Html(settings_templates::lineup_rules(&settings.lineup_rules).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+#[derive(Debug, Deserialize)]
+struct AwardCategoryForm {
+    pub category: String,
+}
+
+async fn create_award_category(
+    State(state): State<AppState>,
+    Form(input): Form<AwardCategoryForm>,
+) -> Result<impl IntoResponse, Error> {
+    let settings = state.svc.add_award_category(&input.category)?;
+    let body = Html(settings_templates::award_categories(&settings.award_categories).into_string());
+
+    Ok((StatusCode::CREATED, body))
+}
+
+async fn delete_award_category(
+    State(state): State<AppState>,
+    Path(index): Path<String>,
+) -> Result<impl IntoResponse, Error> {
+    let index: usize = index
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| Error::InvalidInput("index must be a number".to_string()))?;
+
+    let settings = state.svc.delete_award_category(index)?;
+    let body = Html(settings_templates::award_categories(&settings.award_categories).into_string());
+
+    Ok((StatusCode::OK, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::Fixture;
+    use crate::settings::{Limit, LineupRule};
+    use crate::{Attribute, AxumApp, Config, InMemoryRepo, Service};
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{self, Request, StatusCode},
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt; // for `call`, `oneshot`, `ready`, and `collect`
+
+    #[tokio::test]
+    async fn test_get() {
+        let cfg = Config::default();
+        let repo = Arc::new(InMemoryRepo::new());
+        let svc = Service::new(repo);
+        let app = AxumApp::new(cfg.listen_addr, None, svc).into_router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .uri("/players/1")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_post() {
+        let cfg = Config::default();
+        let repo = Arc::new(InMemoryRepo::new());
+        let svc = Service::new(repo);
         let app = AxumApp::new(cfg.listen_addr, None, svc).into_router();
 
         let response = app
@@ -559,7 +1815,77 @@ mod tests {
 
         assert_eq!(
             body,
-            "<tr><td>1</td><td>foo</td><td>0</td><td>0m 0s</td><td><button class=\"btn danger\" type=\"button\" hx-get=\"/players/0/edit\" hx-trigger=\"click\">EDIT</button></td></tr>"
+            "<tr><td>1</td><td><a href=\"/players/0\">foo</a></td><td></td><td>0</td><td>0m 0s</td><td><button class=\"btn danger\" type=\"button\" hx-get=\"/players/0/edit\" hx-trigger=\"click\">EDIT</button></td></tr>"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_attributes() {
+        let cfg = Config::default();
+        let repo = Arc::new(InMemoryRepo::new());
+        let svc = Service::new(repo.clone());
+        let app = AxumApp::new(cfg.listen_addr, None, svc.clone()).into_router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri("/players")
+                    .header(
+                        http::header::CONTENT_TYPE,
+                        mime::APPLICATION_WWW_FORM_URLENCODED.as_ref(),
+                    )
+                    .body(Body::from(
+                        "name=foo&number=1&position=+guard&attributes=tall&attributes=beginner",
+                    ))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let player = svc.get_player(&0).unwrap();
+        assert_eq!(player.position, "guard");
+        assert_eq!(
+            player.attributes,
+            vec![Attribute::Tall, Attribute::Beginner]
         );
     }
+
+    #[tokio::test]
+    async fn test_sub_on_lineup_warning() {
+        let cfg = Config::default();
+        let repo = Arc::new(InMemoryRepo::new());
+        let svc = Service::new(repo);
+        svc.create_player(1, "foo".to_string()).unwrap();
+        svc.add_lineup_rule(LineupRule {
+            attribute: Attribute::BallHandler,
+            limit: Limit::AtLeast,
+            count: 1,
+        })
+        .unwrap();
+        let game = svc.create_game(Fixture::default()).unwrap();
+        svc.start_game(&game.id).unwrap();
+        let app = AxumApp::new(cfg.listen_addr, None, svc).into_router();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method(http::Method::POST)
+                    .uri(format!("/games/{}/players/0/sub-on", game.id))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let body = std::str::from_utf8(&body).unwrap();
+
+        assert!(body.contains("<div id=\"lineup_warnings\" hx-swap-oob=\"true\">"));
+        assert!(body.contains("at least 1 ball-handler on court, currently 0"));
+    }
 }
@@ -1,21 +1,151 @@
+use super::charts_templates::playing_time;
 use super::icon_templates::{play_svg, stop_svg};
+use super::summary_templates;
+use crate::game::{Lineup, TimelineEntry};
 use crate::{GameState, GameView, PlayerView};
+use chrono::Local;
 use maud::{Markup, PreEscaped, html};
 
+/// `SCORE_POINTS` are the points buttons offered when recording a score.
+pub const SCORE_POINTS: [u32; 3] = [1, 2, 3];
+
+// best lineups shown, there can be many combinations.
+const LINEUPS_SHOWN: usize = 5;
+
+// games are shown in the server's timezone, matching the scheduled input.
+const SCHEDULED_FORMAT: &str = "%a %-d %b %H:%M";
+
+/// `list_games` splits the games into upcoming fixtures, soonest first, and past games.
 pub fn list_games(games: &[GameView]) -> Markup {
-    let rows: Vec<Markup> = games.iter().map(game_table_row).collect();
+    let (mut upcoming, past): (Vec<&GameView>, Vec<&GameView>) =
+        games.iter().partition(|g| g.state.is_upcoming());
+    // games to play straight away have no schedule, so come first.
+    upcoming.sort_by_key(|g| (g.scheduled_at.is_some(), g.scheduled_at, g.id));
+
     html! {
+        h2 class="small" { "Upcoming" }
+        (new_fixture_form())
+        (fixture_table(upcoming.into_iter().map(fixture_table_row).collect()))
         h2 class="small" { "Games" }
-        (game_table(rows))
+        a href="/export/games.csv" {
+            button class="small border" type="button" { "Export CSV" }
+        }
+        (game_table(past.into_iter().map(game_table_row).collect()))
+    }
+}
+
+// new_fixture_form creates a game, leave the fields empty to play straight away.
+fn new_fixture_form() -> Markup {
+    html! {
+        form
+            hx-post="/games"
+            hx-target="#fixtures"
+            hx-swap="afterbegin"
+            hx-on::after-request="if(event.detail.successful) this.reset()"
+        {
+            fieldset {
+                div class="grid" {
+                    div class="s12 m6 l2" {
+                        div class="field border label" {
+                            input type="text" name="round" placeholder="1" class="active" {}
+                            label for="round" class="active" { "Round" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            input type="datetime-local" name="scheduled_at" class="active" {}
+                            label for="scheduled_at" class="active" { "Scheduled" }
+                        }
+                    }
+                    div class="s12 m6 l2" {
+                        div class="field border label" {
+                            input type="text" name="opponent" placeholder="Hawks" class="active" {}
+                            label for="opponent" class="active" { "Opponent" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            input type="text" name="venue" placeholder="Court 1" class="active" {}
+                            label for="venue" class="active" { "Venue" }
+                        }
+                    }
+                    div class="s12 m6 l2" {
+                        div class="field middle-align" {
+                            button type="submit" class="primary small small-elevate" {
+                                "Create Game"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn fixture_table(rows: Vec<Markup>) -> Markup {
+    html! {
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Round" }
+                    th { "Scheduled" }
+                    th { "Opponent" }
+                    th { "Venue" }
+                    th {}
+                }
+            }
+            tbody #fixtures {
+                @for row in rows { (row) }
+            }
+        }
     }
 }
 
+/// `fixture_table_row` is a game yet to start, opening it on match day copies in the current
+/// players.
+pub fn fixture_table_row(game: &GameView) -> Markup {
+    html! {
+        tr {
+            td {
+                a href=(format!("/games/{}", game.id)) {
+                    button class="border small" type="button" { (game.id) }
+                }
+            }
+            td { (or_dash(&game.round)) }
+            td {
+                @match game.scheduled_at {
+                    Some(t) => (t.with_timezone(&Local).format(SCHEDULED_FORMAT)),
+                    None => "-",
+                }
+            }
+            td { (or_dash(&game.opponent)) }
+            td { (or_dash(&game.venue)) }
+            td {
+                @if game.state == GameState::Postponed {
+                    span class="chip small" { "postponed" }
+                } @else {
+                    form method="post" action=(format!("/games/{}/open", game.id)) {
+                        button class="primary small small-elevate" type="submit" { "Open" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn or_dash(value: &str) -> &str {
+    if value.is_empty() { "-" } else { value }
+}
+
 fn game_table(rows: Vec<Markup>) -> Markup {
     html! {
         table class="table" {
             thead {
                 tr {
                     th { "#" }
+                    th { "Opponent" }
+                    th { "Status" }
                     th { "Started" }
                     th { "End" }
                     th { "Total" }
@@ -25,24 +155,13 @@ fn game_table(rows: Vec<Markup>) -> Markup {
                 }
             }
             tbody {
-                tr #new_game {
-                    td {
-                        button
-                            class="primary small small-elevate"
-                            type="button"
-                            hx-post="/games"
-                            hx-target="#new_game"
-                            hx-swap="afterend"
-                        { "new" }
-                    }
-                }
                 @for row in rows { (row) }
             }
         }
     }
 }
 
-pub fn game_table_row(game: &GameView) -> Markup {
+fn game_table_row(game: &GameView) -> Markup {
     html! {
         tr {
             td {
@@ -62,58 +181,74 @@ pub fn game_table_row_columns(game: &GameView) -> String {
 
     let ph = "-".to_string();
 
+    // Opponent, entered by the user so escaped.
+    if game.opponent.is_empty() {
+        columns.push(ph.clone());
+    } else {
+        columns.push(html! { (game.opponent) }.into_string());
+    }
+
+    // Status
+    columns.push(status(game));
+
     // Started
-    match game.state {
-        GameState::NotStarted => {
+    match game.start_time {
+        None => {
             columns.push(ph.clone());
         }
-        GameState::InProgress | GameState::Paused | GameState::Finished => {
+        Some(_) => {
             columns.push(game.start_time_as_digital());
         }
     }
 
     // End
-    match game.state {
-        GameState::NotStarted | GameState::InProgress | GameState::Paused => {
+    match game.end_time {
+        None => {
             columns.push(ph.clone());
         }
-        GameState::Finished => {
+        Some(_) => {
             columns.push(game.end_time_as_digital());
         }
     }
     // Total
     match game.state {
-        GameState::NotStarted => {
-            columns.push(ph.clone());
-        }
-        GameState::InProgress | GameState::Paused | GameState::Finished => {
+        GameState::InProgress | GameState::Paused | GameState::Finished | GameState::Abandoned => {
             columns.push(game.total_duration());
         }
+        GameState::NotStarted
+        | GameState::Cancelled
+        | GameState::Postponed
+        | GameState::Forfeited => {
+            columns.push(ph.clone());
+        }
     }
     // Current
     match game.state {
-        GameState::NotStarted => {
-            columns.push(ph.clone());
-        }
         GameState::InProgress => {
             columns.push(game.current_period_duration());
         }
-        GameState::Paused | GameState::Finished => {
+        GameState::Paused | GameState::Finished | GameState::Abandoned => {
             columns.push("0s".to_string());
         }
+        GameState::NotStarted
+        | GameState::Cancelled
+        | GameState::Postponed
+        | GameState::Forfeited => {
+            columns.push(ph.clone());
+        }
     }
 
     // Period
     columns.push(game.periods.len().to_string());
 
-    // MVP
+    // MVP, the name may come from an imported roster so escaped.
     columns.push(
         game.mvp
             .and_then(|pid| {
                 game.players
                     .iter()
                     .find(|p| p.id == pid)
-                    .map(|p| p.name.clone())
+                    .map(|p| html! { (p.name) }.into_string())
             })
             .unwrap_or_else(|| ph.clone()),
     );
@@ -126,6 +261,28 @@ pub fn game_table_row_columns(game: &GameView) -> String {
         .concat()
 }
 
+/// `status` describes how the game went, or is going, eg: "win 40-30" or "cancelled".
+pub fn status(game: &GameView) -> String {
+    let score = format!("{}-{}", game.score_us, game.score_them);
+    match game.state {
+        GameState::Finished | GameState::Abandoned => {
+            format!(
+                "{} {}",
+                summary_templates::result(game).to_lowercase(),
+                score
+            )
+        }
+        GameState::Forfeited => {
+            format!("forfeit {}", summary_templates::result(game).to_lowercase())
+        }
+        GameState::NotStarted
+        | GameState::InProgress
+        | GameState::Paused
+        | GameState::Cancelled
+        | GameState::Postponed => game.state.to_string(),
+    }
+}
+
 fn game_action_table(rows: Vec<Markup>) -> Markup {
     html! {
         table class="table" {
@@ -161,14 +318,15 @@ pub fn game_action_table_row(game: &GameView) -> Markup {
                             hx-target="#game"
                             hx-swap="outerHTML" { (play_svg()) }
                     }
-                    GameState::InProgress | GameState::Paused | GameState::Finished => {
+                    GameState::InProgress | GameState::Paused | GameState::Finished | GameState::Abandoned => {
                         (game.start_time_as_digital())
                     }
+                    GameState::Cancelled | GameState::Postponed | GameState::Forfeited => { "-" }
                 }
             }
             td { // End
                 @match game.state {
-                    GameState::NotStarted => { "-" }
+                    GameState::NotStarted | GameState::Cancelled | GameState::Postponed | GameState::Forfeited => { "-" }
                     GameState::InProgress | GameState::Paused => {
                         button class="primary small small-elevate error"
                             type="button"
@@ -176,22 +334,22 @@ pub fn game_action_table_row(game: &GameView) -> Markup {
                             hx-target="#game"
                             hx-swap="outerHTML" { (stop_svg()) }
                     }
-                    GameState::Finished => { (game.end_time_as_digital()) }
+                    GameState::Finished | GameState::Abandoned => { (game.end_time_as_digital()) }
                 }
             }
             td { // Total
                 @match game.state {
-                    GameState::NotStarted => { "-" }
-                    GameState::InProgress | GameState::Paused | GameState::Finished => {
+                    GameState::NotStarted | GameState::Cancelled | GameState::Postponed | GameState::Forfeited => { "-" }
+                    GameState::InProgress | GameState::Paused | GameState::Finished | GameState::Abandoned => {
                         (game.total_duration())
                     }
                 }
             }
             td { // Current
                 @match game.state {
-                    GameState::NotStarted => { "-" }
+                    GameState::NotStarted | GameState::Cancelled | GameState::Postponed | GameState::Forfeited => { "-" }
                     GameState::InProgress => { (game.current_period_duration()) }
-                    GameState::Paused | GameState::Finished => { "0s" }
+                    GameState::Paused | GameState::Finished | GameState::Abandoned => { "0s" }
                 }
             }
             td { // Period
@@ -210,7 +368,9 @@ pub fn game_action_table_row(game: &GameView) -> Markup {
                             hx-target="#game"
                             hx-swap="outerHTML"  { (play_svg()) }
                     }
-                    GameState::NotStarted | GameState::Finished => { (game.periods.len()) }
+                    GameState::NotStarted | GameState::Finished | GameState::Cancelled | GameState::Postponed | GameState::Forfeited | GameState::Abandoned => {
+                        (game.periods.len())
+                    }
                 }
             }
             td {  // MVP
@@ -242,20 +402,632 @@ fn mvp_select(game_id: u32, players: &Vec<PlayerView>, mvp: Option<u32>) -> Mark
     }
 }
 
-pub fn get_game(game: &GameView, players: Markup) -> Markup {
+// award_selects picks the winner of each award category, like `mvp_select`.
+fn award_selects(game: &GameView) -> Markup {
+    let base_path = format!("/games/{}/awards", game.id);
+    html! {
+        div class="grid" {
+            @for category in &game.award_categories {
+                @let winner = game.award_winner(category);
+                div class="s12 m6 l3" {
+                    div class="field border label" {
+                        select
+                            name="player_id"
+                            class="active"
+                            hx-put=(base_path)
+                            hx-vals=(serde_json::json!({ "category": category }).to_string())
+                            hx-swap="none"
+                            hx-trigger="input changed"
+                        {
+                            option value="-" selected[winner.is_none()] { "-" }
+                            @for player in &game.players {
+                                @let current = winner == Some(player.id);
+                                option value=(player.id) selected[current] { (player.name) }
+                            }
+                        }
+                        label class="active" { (category) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `voting` lets the coach share a player of the match voting link, watch the votes come in and
+/// confirm the winner as MVP. It refreshes itself while voting is open.
+pub fn voting(game: &GameView) -> Markup {
+    let base_path = format!("/games/{}/voting", game.id);
+    html! {
+        div
+            #voting
+            hx-get=(base_path)
+            hx-trigger={ "every 5s [" (game.voting_open) "]" }
+            hx-swap="outerHTML"
+        {
+            @match &game.voting_token {
+                None => {
+                    @if !matches!(game.state, GameState::NotStarted) {
+                        button
+                            class="primary small small-elevate"
+                            type="button"
+                            hx-post=(base_path)
+                            hx-target="#voting"
+                            hx-swap="outerHTML"
+                        { "Open voting" }
+                    }
+                }
+                Some(token) => {
+                    p {
+                        "Voting link "
+                        a href={ "/vote/" (token) } { "/vote/" (token) }
+                        @if game.voting_open {
+                            " "
+                            button
+                                class="small border"
+                                type="button"
+                                hx-delete=(base_path)
+                                hx-target="#voting"
+                                hx-swap="outerHTML"
+                            { "close voting" }
+                        } @else {
+                            " (closed) "
+                            button
+                                class="small border"
+                                type="button"
+                                hx-post=(base_path)
+                                hx-target="#voting"
+                                hx-swap="outerHTML"
+                            { "reopen voting" }
+                        }
+                    }
+                    table class="table" {
+                        thead {
+                            tr {
+                                th { "Player" }
+                                th { "Votes" }
+                                th { "MVP" }
+                            }
+                        }
+                        tbody {
+                            @for (player_id, votes) in &game.vote_tally {
+                                tr {
+                                    td { (game.player_name(player_id)) }
+                                    td { (votes) }
+                                    td {
+                                        @if game.mvp == Some(*player_id) {
+                                            "MVP"
+                                        } @else {
+                                            button
+                                                class="small border"
+                                                type="button"
+                                                hx-put={ "/games/" (game.id) "/mvp" }
+                                                hx-vals={ "{\"player_id\": " (player_id) "}" }
+                                                hx-target="#game"
+                                                hx-swap="outerHTML"
+                                            { "confirm" }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `vote` is the public player of the match ballot opened from a voting link.
+pub fn vote(game: &GameView, token: &str, current_vote: Option<u32>) -> Markup {
+    html! {
+        div #vote {
+            h2 class="small" { "Player of the match" }
+            @if !game.voting_open {
+                p { "Voting is closed." }
+                @if let Some(mvp) = game.mvp {
+                    p { "Player of the match: " (game.player_name(&mvp)) }
+                }
+            } @else {
+                @if let Some(player_id) = current_vote {
+                    p { "You voted for " (game.player_name(&player_id)) ", vote again to change it." }
+                }
+                form hx-post={ "/vote/" (token) } hx-target="#vote" hx-swap="outerHTML" {
+                    @for player in &game.players {
+                        p {
+                            label class="radio" {
+                                input
+                                    type="radio"
+                                    name="player_id"
+                                    value=(player.id)
+                                    required=""
+                                    checked[current_vote == Some(player.id)] {}
+                                span { "#" (player.number) " " (player.name) }
+                            }
+                        }
+                    }
+                    button type="submit" class="primary small small-elevate" { "Vote" }
+                }
+            }
+        }
+    }
+}
+
+/// `notes` lists the coach's notes about the game and its players, with a form to add more.
+pub fn notes(game: &GameView) -> Markup {
+    let base_path = format!("/games/{}/notes", game.id);
+    html! {
+        div #notes hx-target="#notes" hx-swap="outerHTML" {
+            form hx-post=(base_path) {
+                fieldset {
+                    div class="grid" {
+                        div class="s12 m6 l3" {
+                            div class="field border label" {
+                                select name="player_id" class="active" {
+                                    option value="-" { "Game" }
+                                    @for player in &game.players {
+                                        option value=(player.id) { "#" (player.number) " " (player.name) }
+                                    }
+                                }
+                                label for="player_id" class="active" { "About" }
+                            }
+                        }
+                        div class="s12 m6 l3" {
+                            div class="field border label" {
+                                input
+                                    type="text"
+                                    name="text"
+                                    placeholder="Great defence"
+                                    required=""
+                                    class="active" {}
+                                label for="text" class="active" { "Note" }
+                            }
+                        }
+                        div class="s12 m6 l3" {
+                            div class="field border label" {
+                                input type="text" name="tags" placeholder="defence, effort" class="active" {}
+                                label for="tags" class="active" { "Tags (comma separated)" }
+                            }
+                        }
+                        div class="s12 m6 l3" {
+                            div class="field middle-align" {
+                                button type="submit" class="primary small small-elevate" { "Add Note" }
+                            }
+                        }
+                    }
+                }
+            }
+            @if !game.notes.is_empty() {
+                table class="table" {
+                    thead {
+                        tr {
+                            th { "Time" }
+                            th { "Period" }
+                            th { "About" }
+                            th { "Note" }
+                            th { "Tags" }
+                            th { "Delete" }
+                        }
+                    }
+                    tbody {
+                        @for note in &game.notes {
+                            tr {
+                                td { (note.time.format("%H:%M:%S")) }
+                                td { (note.period) }
+                                td {
+                                    @match (note.player_id, &note.player) {
+                                        (Some(id), Some(player)) => {
+                                            a href={ "/players/" (id) } { (player) }
+                                        }
+                                        _ => { "Game" }
+                                    }
+                                }
+                                td { (note.text) }
+                                td {
+                                    @for tag in &note.tags {
+                                        span class="chip small" { (tag) }
+                                    }
+                                }
+                                td {
+                                    button
+                                        class="btn danger"
+                                        type="button"
+                                        hx-delete={ (base_path) "/" (note.index) }
+                                    { "Delete" }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// incidents logs injuries and other incidents, the player being subbed off so the whole game is
+// refreshed.
+fn incidents(game: &GameView) -> Markup {
+    let base_path = format!("/games/{}/incidents", game.id);
+    html! {
+        @if !game.periods.is_empty() {
+            form hx-post=(base_path) hx-target="#game" hx-swap="outerHTML" {
+                fieldset {
+                    div class="grid" {
+                        div class="s12 m6 l3" {
+                            div class="field border label" {
+                                select name="player_id" class="active" {
+                                    @for player in &game.players {
+                                        option value=(player.id) { "#" (player.number) " " (player.name) }
+                                    }
+                                }
+                                label for="player_id" class="active" { "Player" }
+                            }
+                        }
+                        div class="s12 m6 l6" {
+                            div class="field border label" {
+                                input
+                                    type="text"
+                                    name="description"
+                                    placeholder="Rolled ankle landing"
+                                    required=""
+                                    class="active" {}
+                                label for="description" class="active" { "What happened" }
+                            }
+                        }
+                        div class="s12 m6 l3" {
+                            div class="field middle-align" {
+                                button type="submit" class="primary small small-elevate" {
+                                    "Log Incident"
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        @if !game.incidents.is_empty() {
+            table class="table" {
+                thead {
+                    tr {
+                        th { "Time" }
+                        th { "Game Clock" }
+                        th { "Player" }
+                        th { "What happened" }
+                        th { "Returned" }
+                        th { "Delete" }
+                    }
+                }
+                tbody {
+                    @for incident in &game.incidents {
+                        tr {
+                            td { (incident.time.format("%H:%M:%S")) }
+                            td { (incident.game_clock()) }
+                            td { (incident.player) }
+                            td { (incident.description) }
+                            td { @if incident.returned { "yes" } @else { "no" } }
+                            td {
+                                button
+                                    class="btn danger"
+                                    type="button"
+                                    hx-delete={ (base_path) "/" (incident.index) }
+                                    hx-target="#game"
+                                    hx-swap="outerHTML"
+                                    hx-confirm="Delete this incident?"
+                                { "Delete" }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// lineup_warnings renders a banner for any broken lineup rules. Sub actions only swap their table
+// row so they return this out of band (`oob`) to refresh the banner too.
+pub fn lineup_warnings(warnings: &[String], oob: bool) -> Markup {
+    html! {
+        div #lineup_warnings hx-swap-oob=[oob.then_some("true")] {
+            @if !warnings.is_empty() {
+                article class="error-container" {
+                    @for warning in warnings {
+                        p { (warning) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// game_status shows how many players are on court, the fouls for the current period and whether
+// the opposition is in the bonus.
+pub fn game_status(game: &GameView, oob: bool) -> Markup {
+    html! {
+        p #game_status hx-swap-oob=[oob.then_some("true")] {
+            @if !game.periods.is_empty() {
+                "On court " (game.on_court)
+                @if let Some(allowed) = game.allowed_on_court {
+                    " / " (allowed)
+                }
+                " | Team fouls (period " (game.periods.len()) ") " (game.team_fouls)
+                @if game.in_bonus() {
+                    " " span class="chip small error" { "bonus" }
+                }
+                @if let Some(subs_remaining) = &game.subs_remaining {
+                    " | " (subs_remaining)
+                }
+            }
+        }
+    }
+}
+
+// game_score shows the score with buttons to record the opposition's points, ours being recorded
+// against each scorer in the player actions.
+pub fn game_score(game: &GameView, oob: bool) -> Markup {
+    let base_path = format!("/games/{}/opponent-scores", game.id);
+    let in_progress = matches!(game.state, GameState::InProgress);
+    html! {
+        div #game_score hx-swap-oob=[oob.then_some("true")] {
+            p {
+                "Score " (game.score_us) " - " (game.score_them)
+                @if in_progress {
+                    " | Opposition "
+                    @for points in SCORE_POINTS {
+                        button
+                            class="small border"
+                            type="button"
+                            hx-post=(base_path)
+                            hx-vals={ "{\"points\": " (points) "}" }
+                            hx-target="#game_score"
+                            hx-swap="outerHTML"
+                        { "+" (points) }
+                    }
+                    @if game.score_them > 0 {
+                        button
+                            class="small border"
+                            type="button"
+                            hx-delete=(base_path)
+                            hx-target="#game_score"
+                            hx-swap="outerHTML"
+                        { "-" }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `lineups` lists the best performing player combinations by plus/minus.
+pub fn lineups(lineups: &[Lineup]) -> Markup {
+    html! {
+        table class="table" {
+            thead {
+                tr {
+                    th { "Lineup" }
+                    th { "For" }
+                    th { "Against" }
+                    th { "+/-" }
+                }
+            }
+            tbody {
+                @for lineup in lineups.iter().take(LINEUPS_SHOWN) {
+                    tr {
+                        td { (lineup.players.join(", ")) }
+                        td { (lineup.points_for) }
+                        td { (lineup.points_against) }
+                        td { (format!("{:+}", lineup.plus_minus())) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn timeline(entries: &[TimelineEntry]) -> Markup {
+    html! {
+        table class="table" {
+            thead {
+                tr {
+                    th { "Time" }
+                    th { "Period" }
+                    th { "Event" }
+                }
+            }
+            tbody {
+                @for entry in entries {
+                    tr {
+                        td { (entry.time.format("%H:%M:%S")) }
+                        td { (entry.period) }
+                        td { (entry.description) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// fixture is preserved while the game page polls, so typing isn't lost.
+fn fixture(game: &GameView) -> Markup {
+    let scheduled_at = game
+        .scheduled_at
+        .map(|t| t.with_timezone(&Local).format("%Y-%m-%dT%H:%M").to_string())
+        .unwrap_or_default();
+
+    html! {
+        form
+            #fixture_form
+            hx-preserve=""
+            hx-put={ "/games/" (game.id) "/fixture" }
+            hx-trigger="change"
+            hx-target="#game"
+            hx-swap="outerHTML"
+        {
+            nav class="wrap" {
+                div class="field border label small" {
+                    input type="text" name="round" value=(game.round) class="active" {}
+                    label for="round" class="active" { "Round" }
+                }
+                div class="field border label small" {
+                    input type="text" name="opponent" value=(game.opponent) class="active" {}
+                    label for="opponent" class="active" { "Opponent" }
+                }
+                div class="field border label small" {
+                    input type="datetime-local" name="scheduled_at" value=(scheduled_at) class="active" {}
+                    label for="scheduled_at" class="active" { "Scheduled" }
+                }
+                div class="field border label small" {
+                    input type="text" name="venue" value=(game.venue) class="active" {}
+                    label for="venue" class="active" { "Venue" }
+                }
+            }
+        }
+    }
+}
+
+// outcome_actions change what happens to a game besides being played, eg: postponed for rain.
+fn outcome_actions(game: &GameView) -> Markup {
+    let base_path = format!("/games/{}/", game.id);
+
+    html! {
+        @match game.state {
+            GameState::NotStarted => {
+                nav class="wrap" {
+                    button class="border small" type="button"
+                        hx-post={ (base_path) "postpone" }
+                        hx-target="#game"
+                        hx-swap="outerHTML"
+                        hx-confirm="Postpone this game?" { "Postpone" }
+                    button class="border small" type="button"
+                        hx-post={ (base_path) "cancel" }
+                        hx-target="#game"
+                        hx-swap="outerHTML"
+                        hx-confirm="Cancel this game?" { "Cancel" }
+                    button class="border small" type="button"
+                        hx-post={ (base_path) "forfeit" }
+                        hx-vals=r#"{"team": "them"}"#
+                        hx-target="#game"
+                        hx-swap="outerHTML"
+                        hx-confirm="Record a forfeit win?" { "Forfeit win" }
+                    button class="border small" type="button"
+                        hx-post={ (base_path) "forfeit" }
+                        hx-vals=r#"{"team": "us"}"#
+                        hx-target="#game"
+                        hx-swap="outerHTML"
+                        hx-confirm="Record a forfeit loss?" { "Forfeit loss" }
+                }
+            }
+            GameState::Postponed => {
+                p { "Postponed, set the new scheduled time above then reschedule." }
+                nav class="wrap" {
+                    button class="primary small small-elevate" type="button"
+                        hx-post={ (base_path) "reschedule" }
+                        hx-target="#game"
+                        hx-swap="outerHTML" { "Reschedule" }
+                    button class="border small" type="button"
+                        hx-post={ (base_path) "cancel" }
+                        hx-target="#game"
+                        hx-swap="outerHTML"
+                        hx-confirm="Cancel this game?" { "Cancel" }
+                }
+            }
+            GameState::InProgress | GameState::Paused => {
+                nav class="wrap" {
+                    button class="border small error-text" type="button"
+                        hx-post={ (base_path) "abandon" }
+                        hx-target="#game"
+                        hx-swap="outerHTML"
+                        hx-confirm="Abandon this game? Minutes played so far are kept." { "Abandon" }
+                }
+            }
+            GameState::Cancelled | GameState::Forfeited | GameState::Abandoned => {
+                p { strong { (status(game)) } }
+            }
+            GameState::Finished => {}
+        }
+    }
+}
+
+pub fn get_game(
+    game: &GameView,
+    best_lineups: &[Lineup],
+    players: Markup,
+    shots: Markup,
+) -> Markup {
     let rows = vec![game_action_table_row(game)];
     let base_path = format!("/games/{}", game.id);
     let poll = match game.state {
         GameState::InProgress | GameState::Paused => true,
-        GameState::NotStarted | GameState::Finished => false,
+        GameState::NotStarted
+        | GameState::Finished
+        | GameState::Cancelled
+        | GameState::Postponed
+        | GameState::Forfeited
+        | GameState::Abandoned => false,
     };
     html! {
         div id="game" hx-get=(base_path) hx-trigger={ "every 5s [" (poll) "]" } hx-swap="outerHTML" {
-            h2 class="small" { "Game " (game.id) }
-
+            h2 class="small" {
+                "Game " (game.id)
+                @if !game.opponent.is_empty() { " vs " (game.opponent) }
+            }
+            (fixture(game))
+            (outcome_actions(game))
+            @if game.state.has_ended() {
+                a href={ "/games/" (game.id) "/summary" } {
+                    button class="border small" type="button" { "Summary" }
+                }
+                a href={ "/games/" (game.id) "/report" } {
+                    button class="border small" type="button" { "Report" }
+                }
+                a href={ "/export/games/" (game.id) "/minutes.csv" } {
+                    button class="border small" type="button" { "Minutes CSV" }
+                }
+            }
             (game_action_table(rows))
+            @if !game.award_categories.is_empty() {
+                (award_selects(game))
+            }
+            (lineup_warnings(&game.lineup_warnings, false))
             h3 class="small" { "Players" }
+            p { "Fairness " (game.fairness()) " (share of available time, 100% is even)" }
+            (game_score(game, false))
+            (game_status(game, false))
             (players)
+            @if !game.periods.is_empty() {
+                h3 class="small" { "Playing Time" }
+                (playing_time(game))
+            }
+            @if !best_lineups.is_empty() {
+                h3 class="small" { "Best Lineups" }
+                (lineups(best_lineups))
+            }
+            h3 class="small" { "Notes" }
+            (notes(game))
+            h3 class="small" { "Incidents" }
+            (incidents(game))
+            h3 class="small" { "Player of the match" }
+            (voting(game))
+            h3 class="small" { "Shots" }
+            (shots)
+            h3 class="small" { "Timeline" }
+            (timeline(&game.timeline))
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, Player, Settings};
+
+    #[test]
+    fn test_game_table_row_columns_escaped() {
+        let players = vec![Player::new(1, 7, "<b>Jo</b>".to_string())];
+        let mut game = Game::new(1, players, Settings::default());
+        game.shared.opponent = "Tom & Co".to_string();
+        game.shared.mvp = Some(1);
+
+        let columns = game_table_row_columns(&GameView::from(&game));
+        assert!(columns.starts_with("<td>Tom &amp; Co</td>"));
+        assert!(columns.ends_with("<td>&lt;b&gt;Jo&lt;/b&gt;</td>"));
+    }
+}
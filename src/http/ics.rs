@@ -0,0 +1,174 @@
+//! `ics` writes games as an iCalendar (RFC 5545) feed that phone calendars can subscribe to. Each
+//! game keeps the same UID and counts up its SEQUENCE when saved, so calendars update the event
+//! when a game is rescheduled.
+
+use super::{games_templates, summary_templates};
+use crate::{GameState, GameView};
+use chrono::{DateTime, TimeDelta, Utc};
+
+// games without an end time are shown for an hour.
+const DEFAULT_DURATION_MINUTES: i64 = 60;
+// lines longer than this many octets are folded onto continuation lines.
+const LINE_OCTETS: usize = 75;
+
+// escape encodes a TEXT property value.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | ',' | ';' => {
+                escaped.push('\\');
+                escaped.push(c);
+            }
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// fold splits a content line into CRLF terminated lines of at most 75 octets, continuation lines
+// starting with a space. Splits never fall inside a multi-byte character.
+fn fold(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 2);
+    let mut octets = 0;
+    for c in line.chars() {
+        if octets + c.len_utf8() > LINE_OCTETS {
+            folded.push_str("\r\n ");
+            octets = 1;
+        }
+        folded.push(c);
+        octets += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+// summary is the event title, calendars that ignore STATUS still show what happened.
+fn summary(game: &GameView) -> String {
+    let title = summary_templates::title(game);
+    match game.state {
+        GameState::Cancelled => format!("Cancelled: {}", title),
+        GameState::Postponed => format!("Postponed: {}", title),
+        _ => title,
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+// event returns a game's VEVENT lines, None when the game has no time to show it at.
+fn event(game: &GameView, now: DateTime<Utc>) -> Option<Vec<String>> {
+    let start = game.start_time.or(game.scheduled_at)?;
+    let end = game
+        .end_time
+        .unwrap_or(start + TimeDelta::minutes(DEFAULT_DURATION_MINUTES));
+
+    let mut lines = vec![
+        "BEGIN:VEVENT".to_string(),
+        format!("UID:game-{}@subbers", game.id),
+        format!("DTSTAMP:{}", timestamp(now)),
+        format!("SEQUENCE:{}", game.revision),
+        format!("DTSTART:{}", timestamp(start)),
+        format!("DTEND:{}", timestamp(end)),
+        format!("SUMMARY:{}", escape(&summary(game))),
+    ];
+    if let Some(updated_at) = game.updated_at {
+        lines.push(format!("LAST-MODIFIED:{}", timestamp(updated_at)));
+    }
+    if !game.venue.is_empty() {
+        lines.push(format!("LOCATION:{}", escape(&game.venue)));
+    }
+    if game.state.has_ended() || game.state == GameState::Forfeited {
+        let description = games_templates::status(game);
+        lines.push(format!("DESCRIPTION:{}", escape(&description)));
+    }
+    // calendars show cancelled events struck through, postponed ones as unconfirmed.
+    let status = match game.state {
+        GameState::Cancelled => "CANCELLED",
+        GameState::Postponed => "TENTATIVE",
+        _ => "CONFIRMED",
+    };
+    lines.push(format!("STATUS:{}", status));
+    lines.push("END:VEVENT".to_string());
+
+    Some(lines)
+}
+
+/// `calendar` returns the games that are scheduled or have been played as an iCalendar feed.
+pub fn calendar(name: &str, games: &[GameView]) -> String {
+    let now = Utc::now();
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//subbers//games//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape(name)),
+    ];
+    lines.extend(games.iter().filter_map(|game| event(game, now)).flatten());
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold(line)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Game, Settings};
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape("a, b; c\\d"), "a\\, b\\; c\\\\d");
+        assert_eq!(escape("one\r\ntwo\nthree"), "one\\ntwo\\nthree");
+        assert_eq!(escape("plain"), "plain");
+    }
+
+    #[test]
+    fn test_fold() {
+        assert_eq!(fold("short"), "short\r\n");
+
+        let exact = "a".repeat(LINE_OCTETS);
+        assert_eq!(fold(&exact), format!("{}\r\n", exact));
+
+        let long = "a".repeat(LINE_OCTETS + 10);
+        let folded = fold(&long);
+        let lines: Vec<&str> = folded.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].len(), LINE_OCTETS);
+        assert_eq!(lines[1], format!(" {}", "a".repeat(10)));
+    }
+
+    #[test]
+    fn test_fold_multibyte() {
+        // after "a" the 3 octet characters can't end exactly on the limit.
+        let long = format!("a{}", "€".repeat(40));
+        let folded = fold(&long);
+        let lines: Vec<&str> = folded.split_terminator("\r\n").collect();
+        assert!(lines.iter().all(|l| l.len() <= LINE_OCTETS));
+        assert_eq!(lines[0].len(), 73);
+        assert_eq!(lines.concat().replace(' ', ""), long);
+    }
+
+    #[test]
+    fn test_event_sequence() {
+        let mut game = Game::new(1, vec![], Settings::default());
+        game.shared.scheduled_at = Some(Utc.with_ymd_and_hms(2024, 5, 4, 9, 0, 0).unwrap());
+        let lines = event(&GameView::from(&game), Utc::now()).unwrap();
+        assert!(lines.contains(&"SEQUENCE:0".to_string()));
+        assert!(!lines.iter().any(|l| l.starts_with("LAST-MODIFIED:")));
+
+        // rescheduled and saved twice since.
+        game.shared.scheduled_at = Some(Utc.with_ymd_and_hms(2024, 5, 11, 9, 0, 0).unwrap());
+        game.shared.touch();
+        game.shared.touch();
+        game.shared.updated_at = Some(Utc.with_ymd_and_hms(2024, 5, 1, 18, 30, 0).unwrap());
+        let lines = event(&GameView::from(&game), Utc::now()).unwrap();
+        assert!(lines.contains(&"SEQUENCE:2".to_string()));
+        assert!(lines.contains(&"LAST-MODIFIED:20240501T183000Z".to_string()));
+        assert!(lines.contains(&"DTSTART:20240511T090000Z".to_string()));
+    }
+}
@@ -36,6 +36,14 @@ pub fn body(contents: &Markup) -> Markup {
                     a class="max center-align" href="/" {
                         h6 { "subbers" }
                     }
+                    a class="button transparent" href="/stats" { "Stats" }
+                    a class="button transparent" href="/awards" { "Awards" }
+                    a class="button transparent" href="/plus-minus" { "Plus/Minus" }
+                    a class="button transparent" href="/shots" { "Shots" }
+                    a class="button transparent" href="/discipline" { "Discipline" }
+                    a class="button transparent" href="/incidents" { "Incidents" }
+                    a class="button transparent" href="/reports" { "Reports" }
+                    a class="button transparent" href="/settings" { "Settings" }
                 }
                 hr;
             }
@@ -44,6 +52,15 @@ pub fn body(contents: &Markup) -> Markup {
     }
 }
 
+// public_body omits the navigation, the linked pages requiring a login.
+fn public_body(contents: &Markup) -> Markup {
+    html! {
+        body class="light" {
+            main class="responsive" { (contents) }
+        }
+    }
+}
+
 pub fn games_players(games: &Markup, players: &Markup) -> Markup {
     html! {
         (games)
@@ -61,3 +78,55 @@ pub fn page(title: &str, description: &str, contents: &Markup) -> Markup {
         (footer())
     }
 }
+
+/// `public_page` is a `page` for people without a login, eg: parents voting.
+pub fn public_page(title: &str, description: &str, contents: &Markup) -> Markup {
+    html! {
+        (DOCTYPE)
+        (header(title, description))
+        (public_body(contents))
+        (footer())
+    }
+}
+
+/// `print_page` is a `page` without navigation for printing, elements with the "no-print" class
+/// are hidden when printed.
+pub fn print_page(title: &str, contents: &Markup) -> Markup {
+    html! {
+        (DOCTYPE)
+        (header(title, title))
+        style { "@media print { .no-print { display: none !important; } }" }
+        (public_body(contents))
+        (footer())
+    }
+}
+
+/// `query_value` percent encodes `value` for use in a link's query string, eg: a season label.
+/// Only the RFC 3986 unreserved characters are kept, every other byte of the UTF-8 encoding is
+/// written as `%XX`, so a space becomes `%20` rather than `+` and `&`, `=` and `#` can't break up
+/// the query.
+pub fn query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_value() {
+        assert_eq!(query_value("2024-25_a.b~"), "2024-25_a.b~");
+        assert_eq!(query_value("Spring 2024"), "Spring%202024");
+        assert_eq!(query_value("a&b=c#d+e/f"), "a%26b%3Dc%23d%2Be%2Ff");
+        assert_eq!(query_value("été"), "%C3%A9t%C3%A9");
+        assert_eq!(query_value(""), "");
+    }
+}
@@ -1,7 +1,15 @@
+mod charts_templates;
 mod core;
 mod games_templates;
 mod icon_templates;
+mod ics;
 mod layout_templates;
+mod pdf;
 mod players_templates;
+mod report_templates;
+mod settings_templates;
+mod shots_templates;
+mod stats_templates;
+mod summary_templates;
 
 pub use core::{AxumApp, User};
@@ -0,0 +1,189 @@
+//! `pdf` writes plain text reports as PDF documents. The standard Courier fonts are built into
+//! every PDF reader, so nothing is embedded and columns of text line up.
+
+// A4 in points, with a margin on each side.
+const PAGE_WIDTH: u32 = 595;
+const PAGE_HEIGHT: u32 = 842;
+const MARGIN: u32 = 40;
+const FONT_SIZE: u32 = 9;
+const LEADING: u32 = 11;
+// Courier glyphs are 0.6 of the font size wide.
+const LINE_CHARS: usize = ((PAGE_WIDTH - 2 * MARGIN) * 10 / (FONT_SIZE * 6)) as usize;
+const PAGE_LINES: usize = ((PAGE_HEIGHT - 2 * MARGIN) / LEADING) as usize;
+
+/// `HEADING` prefixes a line to print it in bold.
+pub const HEADING: &str = "## ";
+
+// escape encodes a line as a PDF string in WinAnsiEncoding, which matches Latin-1 for the
+// characters it has. Anything else is replaced with '?'.
+fn escape(line: &str) -> String {
+    line.chars()
+        .map(|c| match c {
+            '\\' | '(' | ')' => format!("\\{c}"),
+            ' '..='~' => c.to_string(),
+            '\u{a0}'..='\u{ff}' => format!("\\{:03o}", c as u32),
+            _ => "?".to_string(),
+        })
+        .collect()
+}
+
+// wrap splits lines too wide for the page, keeping any heading prefix.
+fn wrap(lines: &[String]) -> Vec<(bool, String)> {
+    let mut wrapped = vec![];
+    for line in lines {
+        let (bold, text) = match line.strip_prefix(HEADING) {
+            Some(text) => (true, text),
+            None => (false, line.as_str()),
+        };
+        let chars: Vec<char> = text.chars().collect();
+        if chars.is_empty() {
+            wrapped.push((bold, String::new()));
+        }
+        for chunk in chars.chunks(LINE_CHARS) {
+            wrapped.push((bold, chunk.iter().collect()));
+        }
+    }
+
+    wrapped
+}
+
+fn page_contents(lines: &[(bool, String)]) -> String {
+    let mut contents = format!(
+        "BT\n{} TL\n{} {} Td\n",
+        LEADING,
+        MARGIN,
+        PAGE_HEIGHT - MARGIN - FONT_SIZE
+    );
+    for (bold, line) in lines {
+        let font = if *bold { "F2" } else { "F1" };
+        contents.push_str(&format!(
+            "/{} {} Tf\n({}) Tj\nT*\n",
+            font,
+            FONT_SIZE,
+            escape(line)
+        ));
+    }
+    contents.push_str("ET\n");
+
+    contents
+}
+
+/// `document` lays out the `lines` over as many A4 pages as needed, the `title` in bold first.
+pub fn document(title: &str, lines: &[String]) -> Vec<u8> {
+    let mut all = vec![format!("{HEADING}{title}"), String::new()];
+    all.extend_from_slice(lines);
+    let lines = wrap(&all);
+    let pages: Vec<&[(bool, String)]> = lines.chunks(PAGE_LINES).collect();
+
+    // objects 1 and 2 are the catalog and page tree, 3 and 4 the fonts, then a page and its
+    // contents for each page.
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|i| format!("{} 0 R", 5 + i * 2))
+                .collect::<Vec<String>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier-Bold /Encoding /WinAnsiEncoding >>"
+            .to_string(),
+    ];
+    for (i, page) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            6 + i * 2
+        ));
+        let contents = page_contents(page);
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            contents.len(),
+            contents
+        ));
+    }
+
+    let mut pdf = b"%PDF-1.4\n".to_vec();
+    let mut offsets = vec![];
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.extend_from_slice(format!("{} 0 obj\n{}\nendobj\n", i + 1, object).as_bytes());
+    }
+
+    let xref = pdf.len();
+    pdf.extend_from_slice(
+        format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1).as_bytes(),
+    );
+    for offset in offsets {
+        pdf.extend_from_slice(format!("{:010} 00000 n \n", offset).as_bytes());
+    }
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        )
+        .as_bytes(),
+    );
+
+    pdf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_escape() {
+        assert_eq!(escape(r"a (b) \c"), r"a \(b\) \\c");
+        assert_eq!(escape("café"), r"caf\351");
+        assert_eq!(escape("€5"), "?5");
+    }
+
+    #[test]
+    fn test_wrap() {
+        let long = "x".repeat(LINE_CHARS + 1);
+        let wrapped = wrap(&[format!("{HEADING}{long}"), String::new()]);
+        assert_eq!(
+            wrapped,
+            vec![
+                (true, "x".repeat(LINE_CHARS)),
+                (true, "x".to_string()),
+                (false, String::new()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_document() {
+        let pdf = String::from_utf8(document("Report", &["one".to_string()])).unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Count 1 "));
+        assert!(pdf.contains("/F2 9 Tf\n(Report) Tj"));
+        assert!(pdf.contains("/F1 9 Tf\n(one) Tj"));
+
+        // each xref entry points at the start of its object.
+        let xref = pdf.find("\nxref\n").unwrap() + 1;
+        let startxref: usize = pdf.lines().rev().nth(1).unwrap().parse().unwrap();
+        assert_eq!(startxref, xref);
+        for (i, entry) in pdf[xref..].lines().skip(3).take(6).enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj\n", i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_document_pages() {
+        // the title and blank line take two lines of the first page.
+        let lines = vec!["line".to_string(); PAGE_LINES - 1];
+        let pdf = String::from_utf8(document("Report", &lines)).unwrap();
+        assert!(pdf.contains("/Count 2 "));
+        assert!(pdf.contains("/Kids [5 0 R 7 0 R]"));
+    }
+}
@@ -1,13 +1,133 @@
+use super::games_templates::SCORE_POINTS;
 use super::icon_templates::{pause_svg, play_svg};
-use crate::{GameState, PlayerView};
+use super::layout_templates::query_value;
+use super::stats_templates::{self, season_links};
+use crate::game::{CardKind, NoteEntry};
+use crate::import::{RosterImport, RosterStatus};
+use crate::stats::{PlayerGame, PlayerSeason, PlusMinusTotals};
+use crate::{Attribute, GameState, PlayerView};
 use maud::{Markup, html};
 
-pub fn list_players(players: &[PlayerView]) -> Markup {
+pub fn list_players(
+    players: &[PlayerView],
+    season: &str,
+    plus_minus: &[PlusMinusTotals],
+) -> Markup {
     let rows: Vec<Markup> = players.iter().map(player_table_row).collect();
     html! {
         h2 class="small" { "Players" }
         (new_player_form())
+        button
+            class="small border"
+            type="button"
+            hx-post="/players/rebuild-totals"
+            hx-target="#players"
+            hx-swap="innerHTML"
+            title="Recalculate Count and Total from finished games"
+        { "Rebuild totals" }
+        a href="/export/players.csv" {
+            button class="small border" type="button" { "Export CSV" }
+        }
+        (roster_import_form())
         (player_table(rows))
+        @if !plus_minus.is_empty() {
+            h3 class="small" { a href="/plus-minus" { "Plus/Minus " (season) } }
+            (stats_templates::plus_minus_table(plus_minus))
+        }
+    }
+}
+
+// roster_import_form reads a chosen CSV file into the textarea, which can also be pasted into.
+fn roster_import_form() -> Markup {
+    html! {
+        details {
+            summary { "Import roster CSV" }
+            form hx-post="/players/import/preview" hx-target="#roster_import" hx-swap="outerHTML" {
+                p { "Columns: name, number and optionally position and tags (eg: \"tall beginner\")." }
+                input
+                    type="file"
+                    accept=".csv,text/csv"
+                    onchange="this.files[0].text().then(t => this.form.csv.value = t)";
+                div class="field textarea border" {
+                    textarea name="csv" placeholder="name,number,position,tags" required="" {}
+                }
+                button type="submit" class="primary small small-elevate" { "Preview" }
+            }
+            div #roster_import {}
+        }
+    }
+}
+
+/// `roster_import_preview` shows what importing each row will do, with a button to import.
+pub fn roster_import_preview(csv: &str, previews: &[RosterImport]) -> Markup {
+    let count = previews
+        .iter()
+        .filter(|p| !matches!(p.status, RosterStatus::Duplicate(_)))
+        .count();
+    html! {
+        div #roster_import {
+            table class="table" {
+                thead {
+                    tr {
+                        th { "Line" }
+                        th { "#" }
+                        th { "Name" }
+                        th { "Position" }
+                        th { "Tags" }
+                        th { "Import" }
+                    }
+                }
+                tbody {
+                    @for preview in previews {
+                        tr {
+                            td { (preview.row.line) }
+                            td { (preview.row.number) }
+                            td { (preview.row.name) }
+                            td { (preview.row.position.as_deref().unwrap_or("-")) }
+                            td {
+                                @match &preview.row.attributes {
+                                    Some(attributes) => { (attribute_chips(attributes)) }
+                                    None => { "-" }
+                                }
+                            }
+                            td {
+                                @match &preview.status {
+                                    RosterStatus::New => { "new" }
+                                    RosterStatus::Updated { changes, .. } => {
+                                        "update: " (changes.join(", "))
+                                    }
+                                    RosterStatus::Duplicate(reason) => {
+                                        "duplicate, " (reason)
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            @if count > 0 {
+                form hx-post="/players/import" hx-target="#players" hx-swap="innerHTML" {
+                    input type="hidden" name="csv" value=(csv);
+                    button type="submit" class="primary small small-elevate" {
+                        "Import " (count) " players"
+                    }
+                }
+            } @else {
+                p { "Nothing to import." }
+            }
+        }
+    }
+}
+
+/// `roster_imported` replaces the preview once the roster is imported. It is wrapped in a
+/// template as it's swapped out of band from player table rows.
+pub fn roster_imported(count: usize) -> Markup {
+    html! {
+        template {
+            div #roster_import hx-swap-oob="true" {
+                p { "Imported " (count) " players." }
+            }
+        }
     }
 }
 
@@ -65,6 +185,7 @@ fn player_table(rows: Vec<Markup>) -> Markup {
                 tr {
                     th { "#" }
                     th { "Name" }
+                    th { "Tags" }
                     th { "Count" }
                     th { "Total" }
                     th { "Edit" }
@@ -82,7 +203,13 @@ pub fn player_table_row(player: &PlayerView) -> Markup {
     html! {
         tr {
             td { (player.number) }
-            td { (player.name) }
+            td { a href={ "/players/" (player.id) } { (player.name) } }
+            td {
+                @if !player.position.is_empty() {
+                    span class="chip small fill" { (player.position) }
+                }
+                (attribute_chips(&player.attributes))
+            }
             td { (player.play_count) }
             td { (player.total_duration()) }
             td {
@@ -94,6 +221,14 @@ pub fn player_table_row(player: &PlayerView) -> Markup {
     }
 }
 
+fn attribute_chips(attributes: &[Attribute]) -> Markup {
+    html! {
+        @for attribute in attributes {
+            span class="chip small" { (attribute) }
+        }
+    }
+}
+
 pub fn player_edit_table_row(player: &PlayerView) -> Markup {
     let base_path = format!("/players/{}", player.id);
     html! {
@@ -108,6 +243,21 @@ pub fn player_edit_table_row(player: &PlayerView) -> Markup {
                     input type="text" name="name" value=(player.name) {}
                 }
             }
+            td {
+                div class="field border" {
+                    input type="text" name="position" placeholder="Position" value=(player.position) {}
+                }
+                @for attribute in Attribute::ALL {
+                    label class="checkbox" {
+                        input
+                            type="checkbox"
+                            name="attributes"
+                            value=(attribute)
+                            checked[player.attributes.contains(&attribute)] {}
+                        span { (attribute) }
+                    }
+                }
+            }
             td { (player.play_count) }
             td { (player.total_duration()) }
             td {
@@ -140,6 +290,15 @@ fn player_actions_table(rows: Vec<Markup>) -> Markup {
                     th { "Count" }
                     th { "Total" }
                     th { "Current" }
+                    th { "Rest" }
+                    th { "Longest Rest" }
+                    th { "Bench" }
+                    th { "% Avail" }
+                    th { "Points" }
+                    th { "+/-" }
+                    th { "Fouls" }
+                    th { "Cards" }
+                    th { "Avail" }
                     th { "Sub" }
                 }
             }
@@ -162,19 +321,163 @@ pub fn player_actions_table_row(
             td { (player.play_count) }
             td { (player.total_duration()) }
             td { (player.current_period_duration()) }
-            td { (sub_button(game_id, game_state, &player.id, player.playing)) }
+            // highlight anyone sat on the bench too long so they aren't forgotten.
+            td class=[player.rest_warning.then_some("error-text bold")] { (player.current_rest()) }
+            td { (player.longest_rest()) }
+            td { (player.bench_duration()) }
+            td { (player.available_percentage()) }
+            td { (points(game_id, game_state, player)) }
+            td { (player.plus_minus_signed()) }
+            td { (fouls(game_id, game_state, player)) }
+            td { (cards(game_id, game_state, player)) }
+            td { (availability_button(game_id, game_state, player)) }
+            td { (sub_button(game_id, game_state, player)) }
+        }
+    }
+}
+
+fn points(game_id: &u32, game_state: &GameState, player: &PlayerView) -> Markup {
+    let base_path = format!("/games/{}/players/{}/scores", game_id, player.id);
+    let in_progress = matches!(game_state, GameState::InProgress);
+    html! {
+        (player.points) " "
+        @if in_progress {
+            @for points in SCORE_POINTS {
+                button
+                    class="small border"
+                    type="button"
+                    hx-post=(base_path)
+                    hx-vals={ "{\"points\": " (points) "}" }
+                    hx-target="closest tr"
+                    hx-swap="outerHTML"
+                { "+" (points) }
+            }
+        }
+        @if in_progress && player.points > 0 {
+            button
+                class="small border"
+                type="button"
+                hx-delete=(base_path)
+                hx-target="closest tr"
+                hx-swap="outerHTML"
+            { "-" }
+        }
+    }
+}
+
+fn fouls(game_id: &u32, game_state: &GameState, player: &PlayerView) -> Markup {
+    let base_path = format!("/games/{}/players/{}/fouls", game_id, player.id);
+    let in_progress = matches!(game_state, GameState::InProgress);
+    html! {
+        @if player.fouled_out {
+            span class="chip small error" { (player.fouls) " fouled out" }
+        } @else {
+            span class=[player.foul_trouble.then_some("error-text bold")] { (player.fouls) " " }
+        }
+        @if in_progress && !player.fouled_out {
+            button
+                class="small border"
+                type="button"
+                hx-post=(base_path)
+                hx-target="closest tr"
+                hx-swap="outerHTML"
+            { "+" }
+        }
+        @if in_progress && player.fouls > 0 {
+            button
+                class="small border"
+                type="button"
+                hx-delete=(base_path)
+                hx-target="closest tr"
+                hx-swap="outerHTML"
+            { "-" }
+        }
+    }
+}
+
+fn cards(game_id: &u32, game_state: &GameState, player: &PlayerView) -> Markup {
+    let base_path = format!("/games/{}/players/{}/cards", game_id, player.id);
+    let live = matches!(game_state, GameState::InProgress | GameState::Paused);
+    html! {
+        @for card in &player.cards {
+            span class={ "chip small " (card) } { (card) }
+        }
+        @if player.sent_off {
+            span class="error-text bold" { "sent off " }
+        } @else if player.suspension.is_some() {
+            span class="error-text bold" { "sin bin " (player.suspension_countdown()) " " }
+        }
+        @if live && !player.sent_off {
+            @for kind in CardKind::ALL {
+                button
+                    class="small border"
+                    type="button"
+                    hx-post=(base_path)
+                    hx-vals={ "{\"kind\": \"" (kind) "\"}" }
+                    hx-target="closest tr"
+                    hx-swap="outerHTML"
+                { (kind) }
+            }
         }
+        @if live && !player.cards.is_empty() {
+            button
+                class="small border"
+                type="button"
+                hx-delete=(base_path)
+                hx-target="closest tr"
+                hx-swap="outerHTML"
+            { "-" }
+        }
+    }
+}
+
+// availability_button marks a player late before the game, arrived once they turn up or departed
+// if they leave early.
+fn availability_button(game_id: &u32, game_state: &GameState, player: &PlayerView) -> Markup {
+    let base_path = format!("/games/{}/players/{}/", game_id, player.id);
+    let (action, label) = match (game_state, player.late, player.departed_at) {
+        (
+            GameState::Finished
+            | GameState::Cancelled
+            | GameState::Postponed
+            | GameState::Forfeited
+            | GameState::Abandoned,
+            _,
+            _,
+        ) => return html! { "-" },
+        (_, _, Some(dt)) => return html! { "left " (dt.format("%H:%M")) },
+        (_, true, None) => ("arrive", "arrived"),
+        (GameState::NotStarted, false, None) => ("late", "late"),
+        (_, false, None) => ("depart", "left"),
+    };
+    html! {
+        button
+            class="small border"
+            type="button"
+            hx-post={ (base_path) (action) }
+            hx-target="closest tr"
+            hx-swap="outerHTML"
+        { (label) }
     }
 }
 
-fn sub_button(game_id: &u32, game_state: &GameState, player_id: &u32, playing: bool) -> Markup {
-    let base_path = format!("/games/{}/players/{}/", game_id, player_id);
+fn sub_button(game_id: &u32, game_state: &GameState, player: &PlayerView) -> Markup {
+    let base_path = format!("/games/{}/players/{}/", game_id, player.id);
     // maudfmt panics on @match with | so use rusts match versus maud's @match.
     match game_state {
-        GameState::NotStarted | GameState::Paused | GameState::Finished => html! {
+        GameState::NotStarted
+        | GameState::Paused
+        | GameState::Finished
+        | GameState::Cancelled
+        | GameState::Postponed
+        | GameState::Forfeited
+        | GameState::Abandoned => html! {
             "-"
         },
-        GameState::InProgress => match playing {
+        GameState::InProgress if !player.playing && !player.can_sub_on() => html! {
+            "-"
+        },
+        GameState::InProgress => match player.playing {
             true => html! {
                 button
                     class="primary small small-elevate error"
@@ -196,3 +499,164 @@ fn sub_button(game_id: &u32, game_state: &GameState, player_id: &u32, playing: b
         },
     }
 }
+
+fn season_totals(totals: &PlayerSeason) -> Markup {
+    html! {
+        table class="table" {
+            thead {
+                tr {
+                    th {}
+                    th { "Games" }
+                    th { "Minutes" }
+                    th { "Count" }
+                    th { "Points" }
+                    th { "+/-" }
+                    th { "Fouls" }
+                    th { "Awards" }
+                }
+            }
+            tbody {
+                tr {
+                    td { "Total" }
+                    td { (totals.games.len()) }
+                    td { (totals.played()) }
+                    td { (totals.play_count) }
+                    td { (totals.points) }
+                    td { (format!("{:+}", totals.plus_minus)) }
+                    td { (totals.fouls) }
+                    td { (totals.awards) }
+                }
+                tr {
+                    td { "Per game" }
+                    td { "-" }
+                    td { (totals.average_played()) }
+                    td { (totals.average_play_count()) }
+                    td { (totals.average_points()) }
+                    td { (totals.average_plus_minus()) }
+                    td { "-" }
+                    td { "-" }
+                }
+            }
+        }
+        @if let Some(trend) = totals.minutes_trend() {
+            p { "Minutes trend: " (trend) }
+        }
+    }
+}
+
+fn game_log(games: &[PlayerGame]) -> Markup {
+    html! {
+        table class="table" {
+            thead {
+                tr {
+                    th { "Game" }
+                    th { "Date" }
+                    th { "Minutes" }
+                    th { "Count" }
+                    th { "Stints" }
+                    th { "% Avail" }
+                    th { "Points" }
+                    th { "+/-" }
+                    th { "Fouls" }
+                    th { "Cards" }
+                    th { "Awards" }
+                    th { "Notes" }
+                }
+            }
+            tbody {
+                @for game in games {
+                    tr {
+                        td {
+                            a href={ "/games/" (game.game_id) } { (game.game_id) }
+                            @if game.state != GameState::Finished {
+                                " (" (game.state) ")"
+                            }
+                        }
+                        td {
+                            @if let Some(start_time) = game.start_time {
+                                (start_time.format("%Y-%m-%d"))
+                            }
+                        }
+                        td { (game.played()) }
+                        td { (game.play_count) }
+                        td {
+                            @for stint in &game.stints {
+                                (stint.start_time.format("%H:%M")) "-" (stint.end_time.format("%H:%M")) " "
+                            }
+                        }
+                        td { (game.available_percentage) }
+                        td { (game.points) }
+                        td { (format!("{:+}", game.plus_minus)) }
+                        td { (game.fouls) }
+                        td {
+                            @for card in &game.cards {
+                                span class={ "chip small " (card) } { (card) }
+                            }
+                        }
+                        td { (game.awards.join(", ")) }
+                        td { (game.notes.join("; ")) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `player_page` shows a player's season, their game log and their notes, optionally only the
+/// notes tagged `tag`.
+pub fn player_page(
+    player: &PlayerView,
+    season: &str,
+    seasons: &[String],
+    totals: &PlayerSeason,
+    notes: &[NoteEntry],
+    tag: Option<&str>,
+) -> Markup {
+    let base_path = format!("/players/{}", player.id);
+    html! {
+        h2 class="small" { "#" (player.number) " " (player.name) }
+        (attribute_chips(&player.attributes))
+        p { "All time: " (player.play_count) " subs on, " (player.total_duration()) " played." }
+        (season_links(&base_path, season, seasons))
+        h3 class="small" { "Season " (season) }
+        (season_totals(totals))
+        h3 class="small" { "Games" }
+        (game_log(&totals.games))
+        h3 class="small" { "Notes " (season) }
+        @if let Some(tag) = tag {
+            p {
+                "Tagged " span class="chip small fill" { (tag) } " "
+                a href={ (base_path) "?season=" (query_value(season)) } { "show all" }
+            }
+        }
+        table class="table" {
+            thead {
+                tr {
+                    th { "Date" }
+                    th { "Game" }
+                    th { "Period" }
+                    th { "Note" }
+                    th { "Tags" }
+                }
+            }
+            tbody {
+                @for note in notes {
+                    tr {
+                        td { (note.time.format("%Y-%m-%d %H:%M")) }
+                        td { a href={ "/games/" (note.game_id) } { (note.game_id) } }
+                        td { (note.period) }
+                        td { (note.text) }
+                        td {
+                            @for note_tag in &note.tags {
+                                a
+                                    class="chip small"
+                                    href={ (base_path) "?season=" (query_value(season)) "&tag=" (query_value(note_tag)) }
+                                { (note_tag) }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
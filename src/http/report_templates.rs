@@ -0,0 +1,296 @@
+use super::pdf::HEADING;
+use super::stats_templates::season_links;
+use super::summary_templates::{awards, result, title};
+use crate::GameView;
+use crate::game::Participation;
+use crate::stats::SeasonParticipation;
+use chrono::TimeDelta;
+use maud::{Markup, html};
+
+// reports are printed so names are cut to keep the columns of the PDF aligned.
+const NAME_WIDTH: usize = 18;
+
+fn clock(delta: TimeDelta) -> String {
+    format!("{}:{:02}", delta.num_minutes(), delta.num_seconds() % 60)
+}
+
+fn compliance(compliant: Option<bool>) -> &'static str {
+    match compliant {
+        Some(true) => "yes",
+        Some(false) => "NO",
+        None => "-",
+    }
+}
+
+fn rule(min_periods: u32, min_minutes: u32) -> String {
+    match (min_periods, min_minutes) {
+        (0, 0) => "Minimum participation: none set".to_string(),
+        (p, 0) => format!("Minimum participation: {} period(s)", p),
+        (0, m) => format!("Minimum participation: {} minute(s)", m),
+        (p, m) => format!("Minimum participation: {} period(s) and {} minute(s)", p, m),
+    }
+}
+
+fn name(number: u32, name: &str) -> String {
+    format!("#{} {}", number, name)
+        .chars()
+        .take(NAME_WIDTH)
+        .collect()
+}
+
+fn print_buttons(pdf_href: &str) -> Markup {
+    html! {
+        nav class="no-print" {
+            button class="primary small small-elevate" type="button" onclick="window.print()" { "Print" }
+            a href=(pdf_href) {
+                button class="border small" type="button" { "PDF" }
+            }
+        }
+    }
+}
+
+/// `game_report` is the printable record of a game's minutes, by period, and awards.
+pub fn game_report(game: &GameView, participation: &[Participation]) -> Markup {
+    html! {
+        (print_buttons(&format!("/games/{}/report.pdf", game.id)))
+        h2 class="small" { (title(game)) }
+        p {
+            (game.start_time.map(|st| st.format("%Y-%m-%d %H:%M").to_string()).unwrap_or_default())
+            " | " (result(game)) " " (game.score_us) " - " (game.score_them)
+            " | " (game.total_duration())
+        }
+        p { (rule(game.min_periods, game.min_minutes)) }
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Name" }
+                    @for i in 1..=game.periods.len() {
+                        th { "P" (i) }
+                    }
+                    th { "Total" }
+                    th { "Min Met" }
+                }
+            }
+            tbody {
+                @for p in participation {
+                    tr {
+                        td { (p.number) }
+                        td { (p.name) @if !p.attended { " (absent)" } }
+                        @for played in &p.period_played {
+                            td { (clock(*played)) }
+                        }
+                        td { (clock(p.played)) }
+                        td { (compliance(p.compliant)) }
+                    }
+                }
+            }
+        }
+        @let awards = awards(game);
+        @if !awards.is_empty() {
+            h3 class="small" { "Awards" }
+            ul class="list" {
+                @for (category, name) in awards {
+                    li { strong { (category) } " " (name) }
+                }
+            }
+        }
+    }
+}
+
+/// `game_report_text` is the `game_report` as lines of text for a PDF.
+pub fn game_report_text(game: &GameView, participation: &[Participation]) -> Vec<String> {
+    let mut lines = vec![
+        format!(
+            "{} | {} {} - {} | {}",
+            game.start_time
+                .map(|st| st.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default(),
+            result(game),
+            game.score_us,
+            game.score_them,
+            game.total_duration()
+        ),
+        rule(game.min_periods, game.min_minutes),
+        String::new(),
+        format!("{HEADING}Minutes"),
+    ];
+
+    let mut header = format!("{:<width$}", "Player", width = NAME_WIDTH + 1);
+    for i in 1..=game.periods.len() {
+        header.push_str(&format!("{:>7}", format!("P{}", i)));
+    }
+    header.push_str(&format!("{:>8}{:>9}", "Total", "Min Met"));
+    lines.push(header);
+
+    for p in participation {
+        let mut line = format!(
+            "{:<width$}",
+            name(p.number, &p.name),
+            width = NAME_WIDTH + 1
+        );
+        for played in &p.period_played {
+            line.push_str(&format!("{:>7}", clock(*played)));
+        }
+        line.push_str(&format!("{:>8}", clock(p.played)));
+        line.push_str(&format!(
+            "{:>9}",
+            if p.attended {
+                compliance(p.compliant)
+            } else {
+                "absent"
+            }
+        ));
+        lines.push(line);
+    }
+
+    let awards = awards(game);
+    if !awards.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("{HEADING}Awards"));
+        for (category, name) in awards {
+            lines.push(format!("{}: {}", category, name));
+        }
+    }
+
+    lines
+}
+
+fn compliant_games(total: &SeasonParticipation) -> String {
+    format!(
+        "{}/{}",
+        total.compliant,
+        total.compliant as usize + total.non_compliant.len()
+    )
+}
+
+fn short_games(total: &SeasonParticipation) -> String {
+    total
+        .non_compliant
+        .iter()
+        .map(u32::to_string)
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// `season_report` is the printable record of each player's minutes, awards and minimum
+/// participation across the season's finished and abandoned games.
+pub fn season_report(
+    season: &str,
+    seasons: &[String],
+    games: &[GameView],
+    totals: &[SeasonParticipation],
+) -> Markup {
+    html! {
+        div class="no-print" { (season_links("/reports", season, seasons)) }
+        (print_buttons(&format!(
+            "/reports/season.pdf?season={}",
+            super::layout_templates::query_value(season)
+        )))
+        h2 class="small" { "Season " (season) " Report" }
+        h3 class="small" { "Games" }
+        table class="table" {
+            thead {
+                tr {
+                    th { "Game" }
+                    th { "Date" }
+                    th { "Opponent" }
+                    th { "Result" }
+                    th { "Score" }
+                    th { "Report" }
+                }
+            }
+            tbody {
+                @for game in games {
+                    tr {
+                        td { (game.id) }
+                        td { (game.start_time.map(|st| st.format("%Y-%m-%d").to_string()).unwrap_or_default()) }
+                        td { (game.opponent) }
+                        td { (result(game)) }
+                        td { (game.score_us) " - " (game.score_them) }
+                        td { a href={ "/games/" (game.id) "/report" } { "report" } }
+                    }
+                }
+            }
+        }
+        h3 class="small" { "Players" }
+        p { "Minimum participation is checked against each game's settings." }
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Name" }
+                    th { "Games" }
+                    th { "Minutes" }
+                    th { "Awards" }
+                    th { "Min Met" }
+                    th { "Short Games" }
+                }
+            }
+            tbody {
+                @for total in totals {
+                    tr {
+                        td { (total.number) }
+                        td { (total.name) }
+                        td { (total.games) }
+                        td { (clock(total.played)) }
+                        td { (total.awards) }
+                        td { (compliant_games(total)) }
+                        td { (short_games(total)) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// `season_report_text` is the `season_report` as lines of text for a PDF.
+pub fn season_report_text(games: &[GameView], totals: &[SeasonParticipation]) -> Vec<String> {
+    let mut lines = vec![
+        format!("{HEADING}Games"),
+        format!(
+            "{:<6}{:<12}{:<24}{:<10}{:>9}",
+            "Game", "Date", "Opponent", "Result", "Score"
+        ),
+    ];
+    for game in games {
+        lines.push(format!(
+            "{:<6}{:<12}{:<24}{:<10}{:>9}",
+            game.id,
+            game.start_time
+                .map(|st| st.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            game.opponent.chars().take(22).collect::<String>(),
+            result(game),
+            format!("{} - {}", game.score_us, game.score_them)
+        ));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("{HEADING}Players"));
+    lines.push("Minimum participation is checked against each game's settings.".to_string());
+    lines.push(format!(
+        "{:<width$}{:>6}{:>9}{:>8}{:>9}  {}",
+        "Player",
+        "Games",
+        "Minutes",
+        "Awards",
+        "Min Met",
+        "Short Games",
+        width = NAME_WIDTH + 1
+    ));
+    for total in totals {
+        lines.push(format!(
+            "{:<width$}{:>6}{:>9}{:>8}{:>9}  {}",
+            name(total.number, &total.name),
+            total.games,
+            clock(total.played),
+            total.awards,
+            compliant_games(total),
+            short_games(total),
+            width = NAME_WIDTH + 1
+        ));
+    }
+
+    lines
+}
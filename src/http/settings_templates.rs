@@ -0,0 +1,364 @@
+use super::layout_templates;
+use crate::Attribute;
+use crate::dump::ImportSummary;
+use crate::settings::{Limit, LineupRule, Settings, SubRule, Surface};
+use maud::{Markup, html};
+
+pub fn get_settings(settings: &Settings, calendar_token: &str) -> Markup {
+    html! {
+        h2 class="small" { "Settings" }
+        p { "Settings are copied into each new game, existing games are not changed." }
+        h3 class="small" { "Game" }
+        (settings_form(settings))
+        h3 class="small" { "Lineup Rules" }
+        (new_lineup_rule_form())
+        (lineup_rules(&settings.lineup_rules))
+        h3 class="small" { "Awards" }
+        p { "Awards given each game alongside the MVP." }
+        (new_award_category_form())
+        (award_categories(&settings.award_categories))
+        h3 class="small" { "Calendar" }
+        p {
+            "Share the calendar link with parents to subscribe to scheduled games in their phone "
+            "calendars. Resetting the link stops the old one working."
+        }
+        (calendar(calendar_token))
+        h3 class="small" { "Backup" }
+        p {
+            "Download all players, games and settings as JSON to move to another machine. "
+            "Users aren't included, set them with --basic-auth."
+        }
+        a href="/export/dump.json" {
+            button class="border small" type="button" { "Download JSON" }
+        }
+        (import_dump_form())
+    }
+}
+
+// import_dump_form reads a chosen JSON export into the hidden field, replace being confirmed.
+fn import_dump_form() -> Markup {
+    html! {
+        form
+            hx-post="/import/dump"
+            hx-target="#dump_import"
+            hx-swap="outerHTML"
+            hx-confirm="Import this JSON export?"
+        {
+            fieldset {
+                div class="grid" {
+                    div class="s12 m6 l3" {
+                        input
+                            type="file"
+                            accept=".json,application/json"
+                            required=""
+                            onchange="this.files[0].text().then(t => this.form.json.value = t)";
+                        input type="hidden" name="json";
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            select name="mode" class="active" {
+                                option value="merge" selected { "merge, add to existing" }
+                                option value="replace" { "replace everything" }
+                            }
+                            label for="mode" class="active" { "Mode" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field middle-align" {
+                            button type="submit" class="primary small small-elevate" { "Import JSON" }
+                        }
+                    }
+                }
+            }
+        }
+        div #dump_import {}
+    }
+}
+
+pub fn calendar(token: &str) -> Markup {
+    let href = format!(
+        "/calendar.ics?token={}",
+        layout_templates::query_value(token)
+    );
+
+    html! {
+        div #calendar {
+            @if token.is_empty() {
+                p { "The calendar feed is disabled." }
+            } @else {
+                p { a class="link" href=(href) { "Calendar link" } }
+            }
+            nav {
+                button
+                    class="border small"
+                    hx-post="/settings/calendar"
+                    hx-target="#calendar"
+                    hx-swap="outerHTML"
+                {
+                    @if token.is_empty() { "Enable" } @else { "Reset link" }
+                }
+                @if !token.is_empty() {
+                    button
+                        class="border small"
+                        hx-delete="/settings/calendar"
+                        hx-target="#calendar"
+                        hx-swap="outerHTML"
+                        hx-confirm="Disable the calendar feed?"
+                    { "Disable" }
+                }
+            }
+        }
+    }
+}
+
+pub fn dump_imported(summary: &ImportSummary) -> Markup {
+    html! {
+        div #dump_import {
+            p { (summary.to_string()) "." }
+        }
+    }
+}
+
+fn number_field(name: &str, label: &str, value: u32) -> Markup {
+    html! {
+        div class="s12 m6 l3" {
+            div class="field border label" {
+                input
+                    type="number"
+                    pattern="\\d+"
+                    name=(name)
+                    value=(value)
+                    required=""
+                    class="active" {}
+                label for=(name) class="active" { (label) }
+            }
+        }
+    }
+}
+
+fn text_field(name: &str, label: &str, value: &str) -> Markup {
+    html! {
+        div class="s12 m6 l3" {
+            div class="field border label" {
+                input type="text" name=(name) value=(value) required="" class="active" {}
+                label for=(name) class="active" { (label) }
+            }
+        }
+    }
+}
+
+pub fn settings_form(settings: &Settings) -> Markup {
+    html! {
+        form hx-put="/settings" hx-swap="outerHTML" {
+            fieldset {
+                div class="grid" {
+                    (text_field("season", "Season", &settings.season))
+                    (number_field(
+                        "on_court_limit",
+                        "Players on court (0 unlimited)",
+                        settings.on_court_limit,
+                    ))
+                    (number_field(
+                        "bench_warning_minutes",
+                        "Bench warning (minutes, 0 off)",
+                        settings.bench_warning_minutes,
+                    ))
+                    (number_field(
+                        "foul_out_limit",
+                        "Foul out limit (0 off)",
+                        settings.foul_out_limit,
+                    ))
+                    (number_field(
+                        "team_foul_bonus",
+                        "Team fouls for bonus (0 off)",
+                        settings.team_foul_bonus,
+                    ))
+                    (number_field(
+                        "green_card_minutes",
+                        "Green card sin bin (minutes)",
+                        settings.green_card_minutes,
+                    ))
+                    (number_field(
+                        "yellow_card_minutes",
+                        "Yellow card sin bin (minutes)",
+                        settings.yellow_card_minutes,
+                    ))
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            select name="sub_rule" class="active" {
+                                @for rule in SubRule::ALL {
+                                    option value=(rule) selected[settings.sub_rule == rule] { (rule) }
+                                }
+                            }
+                            label for="sub_rule" class="active" { "Substitutions" }
+                        }
+                    }
+                    (number_field(
+                        "sub_limit",
+                        "Max subs or windows",
+                        settings.sub_limit,
+                    ))
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            select name="surface" class="active" {
+                                @for surface in Surface::ALL {
+                                    option value=(surface) selected[settings.surface == surface] { (surface) }
+                                }
+                            }
+                            label for="surface" class="active" { "Shot chart" }
+                        }
+                    }
+                    (number_field("min_periods", "Min periods played", settings.min_periods))
+                    (number_field("min_minutes", "Min minutes played", settings.min_minutes))
+                    div class="s12 m6 l3" {
+                        div class="field middle-align" {
+                            button type="submit" class="primary small small-elevate" { "Save" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn new_lineup_rule_form() -> Markup {
+    html! {
+        form
+            hx-post="/settings/lineup-rules"
+            hx-target="#lineup_rules"
+            hx-swap="outerHTML"
+            hx-on::after-request="if(event.detail.successful) this.reset()"
+        {
+            fieldset {
+                div class="grid" {
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            select name="limit" class="active" {
+                                option value="at-least" { (Limit::AtLeast) }
+                                option value="at-most" { (Limit::AtMost) }
+                            }
+                            label for="limit" class="active" { "Limit" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            input
+                                type="number"
+                                pattern="\\d+"
+                                placeholder="1"
+                                name="count"
+                                required=""
+                                class="active" {}
+                            label for="count" class="active" { "Count" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            select name="attribute" class="active" {
+                                @for attribute in Attribute::ALL {
+                                    option value=(attribute) { (attribute) }
+                                }
+                            }
+                            label for="attribute" class="active" { "Attribute" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field middle-align" {
+                            button type="submit" class="primary small small-elevate" {
+                                "Add Rule"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn lineup_rules(rules: &[LineupRule]) -> Markup {
+    html! {
+        table #lineup_rules class="table" hx-target="#lineup_rules" hx-swap="outerHTML" {
+            thead {
+                tr {
+                    th { "Rule" }
+                    th { "Delete" }
+                }
+            }
+            tbody {
+                @for (index, rule) in rules.iter().enumerate() {
+                    tr {
+                        td { (rule) " on court" }
+                        td {
+                            button
+                                class="btn danger"
+                                type="button"
+                                hx-delete=(format!("/settings/lineup-rules/{}", index))
+                            { "Delete" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn new_award_category_form() -> Markup {
+    html! {
+        form
+            hx-post="/settings/award-categories"
+            hx-target="#award_categories"
+            hx-swap="outerHTML"
+            hx-on::after-request="if(event.detail.successful) this.reset()"
+        {
+            fieldset {
+                div class="grid" {
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            input
+                                type="text"
+                                name="category"
+                                placeholder="Best Defender"
+                                required=""
+                                class="active" {}
+                            label for="category" class="active" { "Award" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field middle-align" {
+                            button type="submit" class="primary small small-elevate" {
+                                "Add Award"
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn award_categories(categories: &[String]) -> Markup {
+    html! {
+        table #award_categories class="table" hx-target="#award_categories" hx-swap="outerHTML" {
+            thead {
+                tr {
+                    th { "Award" }
+                    th { "Delete" }
+                }
+            }
+            tbody {
+                @for (index, category) in categories.iter().enumerate() {
+                    tr {
+                        td { (category) }
+                        td {
+                            button
+                                class="btn danger"
+                                type="button"
+                                hx-delete=(format!("/settings/award-categories/{}", index))
+                            { "Delete" }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
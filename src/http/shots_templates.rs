@@ -0,0 +1,192 @@
+use super::layout_templates::query_value;
+use super::stats_templates::season_links;
+use crate::game::Shot;
+use crate::settings::Surface;
+use crate::stats::ShootingTotals;
+use crate::{GameState, GameView};
+use maud::{Markup, html};
+
+const MADE_COLOUR: &str = "#2e7d32";
+const MISSED_COLOUR: &str = "#c62828";
+
+// markings draws the lines of the `surface`'s attacking end, roughly to scale.
+fn markings(surface: Surface) -> Markup {
+    html! {
+        rect x="0" y="0" width=(surface.width()) height=(surface.length()) {}
+        @match surface {
+            Surface::Basketball => {
+                rect x="34" y="0" width="32" height="38" {}
+                circle cx="50" cy="38" r="12" {}
+                circle cx="50" cy="10" r="1.5" {}
+                path d="M 6 0 L 6 28 A 47.5 47.5 0 0 0 94 28 L 94 0" {}
+            }
+            Surface::Netball => {
+                path d="M 18 0 A 32 32 0 0 0 82 0" {}
+                circle cx="50" cy="1.5" r="1.5" {}
+            }
+            Surface::Football => {
+                line x1="44.5" y1="0" x2="55.5" y2="0" stroke-width="2" {}
+                rect x="36.5" y="0" width="27" height="8" {}
+                rect x="20" y="0" width="60" height="24" {}
+                circle cx="50" cy="16" r="0.8" {}
+                path d="M 39 24 A 13.5 13.5 0 0 0 61 24" {}
+                path d="M 36.5 77 A 13.5 13.5 0 0 1 63.5 77" {}
+            }
+            Surface::Plain => {}
+        }
+    }
+}
+
+// court_svg draws the attacking end of the `surface` with the goal at the top, marking each shot
+// made with a dot and each miss with a cross.
+fn court_svg(surface: Surface, shots: &[Shot]) -> Markup {
+    html! {
+        svg
+            #shot_court
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox={ "0 0 " (surface.width()) " " (surface.length()) }
+            width="100%"
+            style="max-width: 480px"
+        {
+            g fill="none" stroke="currentColor" stroke-width="0.5" {
+                (markings(surface))
+            }
+            @for shot in shots {
+                @if shot.made {
+                    circle cx=(shot.x) cy=(shot.y) r="1.8" fill=(MADE_COLOUR) {}
+                } @else {
+                    path
+                        d={
+                            "M " (shot.x.saturating_sub(2)) " " (shot.y.saturating_sub(2)) " l 4 4 m 0 -4 l -4 4"
+                        }
+                        stroke=(MISSED_COLOUR)
+                        stroke-width="0.8" {}
+                }
+            }
+        }
+    }
+}
+
+// shot_location is the htmx `hx-vals` converting a click on the court into the surface's units.
+fn shot_location(surface: Surface) -> String {
+    let rect = "document.getElementById('shot_court').getBoundingClientRect()";
+    let (width, length) = (surface.width(), surface.length());
+    format!(
+        "js:{{x: Math.round((event.clientX - {rect}.left) / {rect}.width * {width}), \
+         y: Math.round((event.clientY - {rect}.top) / {rect}.height * {length})}}"
+    )
+}
+
+/// `shooting_bars` charts each player's shooting percentage, with made/attempts alongside.
+pub fn shooting_bars(totals: &[ShootingTotals]) -> Markup {
+    let row_height = 20;
+    let bar_width = 200.0;
+    html! {
+        svg
+            xmlns="http://www.w3.org/2000/svg"
+            viewBox={ "0 0 420 " (totals.len() * row_height) }
+            width="100%"
+            style="max-width: 480px"
+        {
+            @for (i, total) in totals.iter().enumerate() {
+                @let y = i * row_height;
+                text x="0" y=(y + 14) font-size="12" fill="currentColor" {
+                    "#" (total.number) " " (total.name)
+                }
+                rect x="120" y=(y + 4) width=(bar_width) height="12" fill="none" stroke="currentColor" stroke-width="0.5" {}
+                rect x="120" y=(y + 4) width=(format!("{:.1}", total.ratio() * bar_width)) height="12" fill=(MADE_COLOUR) {}
+                text x="330" y=(y + 14) font-size="12" fill="currentColor" {
+                    (total.made) "/" (total.attempts) " " (total.percentage())
+                }
+            }
+        }
+    }
+}
+
+/// `shot_chart` shows the game's shots. While a period is being played tapping the court records a
+/// shot for the selected shooter.
+pub fn shot_chart(game: &GameView) -> Markup {
+    let base_path = format!("/games/{}/shots", game.id);
+    let in_progress = matches!(game.state, GameState::InProgress);
+    html! {
+        div #shot_chart {
+            @if in_progress {
+                // preserved so the shooter picked isn't reset by each shot or the page refreshing.
+                form #shot_form hx-preserve {
+                    div class="grid" {
+                        div class="s12 m6 l3" {
+                            div class="field border label" {
+                                select name="player_id" class="active" {
+                                    @for player in &game.players {
+                                        option value=(player.id) { "#" (player.number) " " (player.name) }
+                                    }
+                                }
+                                label for="player_id" class="active" { "Shooter" }
+                            }
+                        }
+                        div class="s12 m6 l3 middle-align" {
+                            label class="radio" {
+                                input type="radio" name="made" value="true" checked {}
+                                span { "made" }
+                            }
+                            label class="radio" {
+                                input type="radio" name="made" value="false" {}
+                                span { "missed" }
+                            }
+                        }
+                    }
+                }
+                @if !game.shots.is_empty() {
+                    button
+                        class="small border"
+                        type="button"
+                        hx-delete=(base_path)
+                        hx-target="#shot_chart"
+                        hx-swap="outerHTML"
+                    { "undo shot" }
+                }
+                div
+                    hx-post=(base_path)
+                    hx-trigger="click"
+                    hx-include="#shot_form"
+                    hx-vals=(shot_location(game.surface))
+                    hx-target="#shot_chart"
+                    hx-swap="outerHTML"
+                { (court_svg(game.surface, &game.shots)) }
+            } @else {
+                (court_svg(game.surface, &game.shots))
+            }
+            (shooting_bars(&game.shooting))
+        }
+    }
+}
+
+/// `season_shots` charts the season's shots on the current `surface`.
+pub fn season_shots(
+    season: &str,
+    seasons: &[String],
+    surface: Surface,
+    player_id: Option<u32>,
+    shots: &[Shot],
+    totals: &[ShootingTotals],
+) -> Markup {
+    html! {
+        h2 class="small" { "Shots " (season) }
+        (season_links("/shots", season, seasons))
+        nav class="scroll" {
+            a class={ "chip small" @if player_id.is_none() { " fill" } } href={ "/shots?season=" (query_value(season)) } {
+                "everyone"
+            }
+            @for total in totals {
+                @let current = player_id == Some(total.player_id);
+                a
+                    class={ "chip small" @if current { " fill" } }
+                    href={ "/shots?season=" (query_value(season)) "&player_id=" (total.player_id) }
+                { (total.name) }
+            }
+        }
+        (court_svg(surface, shots))
+        h3 class="small" { "Shooting" }
+        (shooting_bars(totals))
+    }
+}
@@ -0,0 +1,299 @@
+use super::charts_templates::{minutes_heatmap, player_trends};
+use super::games_templates::lineups;
+use super::layout_templates::query_value;
+use crate::game::{IncidentEntry, Lineup};
+use crate::stats::{
+    AwardTally, DisciplineTotals, LeaderboardFilter, LeaderboardRow, LeaderboardSort,
+    PlusMinusTotals, SeasonMinutes,
+};
+use maud::{Markup, html};
+
+// season_links lets the user switch between seasons for `base_path`.
+pub fn season_links(base_path: &str, season: &str, seasons: &[String]) -> Markup {
+    html! {
+        nav class="scroll" {
+            @for s in seasons {
+                @let current = s == season;
+                a
+                    class={ "chip small" @if current { " fill" } }
+                    href={ (base_path) "?season=" (query_value(s)) }
+                { (s) }
+            }
+        }
+    }
+}
+
+// leaderboard_href keeps the filters when changing the sort column.
+fn leaderboard_href(filter: &LeaderboardFilter, sort: LeaderboardSort) -> String {
+    let date = |d: Option<chrono::NaiveDate>| d.map(|d| d.to_string()).unwrap_or_default();
+    format!(
+        "/stats?season={}&from={}&to={}&opponent={}&sort={}",
+        query_value(&filter.season),
+        date(filter.from),
+        date(filter.to),
+        query_value(filter.opponent.as_deref().unwrap_or_default()),
+        sort
+    )
+}
+
+fn sort_header(
+    filter: &LeaderboardFilter,
+    current: LeaderboardSort,
+    sort: LeaderboardSort,
+    label: &str,
+) -> Markup {
+    html! {
+        th {
+            a href=(leaderboard_href(filter, sort)) {
+                (label) @if sort == current { " ▼" }
+            }
+        }
+    }
+}
+
+pub fn leaderboard(
+    filter: &LeaderboardFilter,
+    sort: LeaderboardSort,
+    seasons: &[String],
+    opponents: &[String],
+    rows: &[LeaderboardRow],
+    minutes: &SeasonMinutes,
+) -> Markup {
+    let date = |d: Option<chrono::NaiveDate>| d.map(|d| d.to_string()).unwrap_or_default();
+    html! {
+        h2 class="small" { "Stats " (filter.season) }
+        form method="get" action="/stats" {
+            input type="hidden" name="sort" value=(sort);
+            fieldset {
+                div class="grid" {
+                    div class="s12 m6 l2" {
+                        div class="field border label" {
+                            select name="season" class="active" {
+                                @for s in seasons {
+                                    option value=(s) selected[*s == filter.season] { (s) }
+                                }
+                            }
+                            label for="season" class="active" { "Season" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            input type="date" name="from" value=(date(filter.from)) class="active";
+                            label for="from" class="active" { "From" }
+                        }
+                    }
+                    div class="s12 m6 l3" {
+                        div class="field border label" {
+                            input type="date" name="to" value=(date(filter.to)) class="active";
+                            label for="to" class="active" { "To" }
+                        }
+                    }
+                    div class="s12 m6 l2" {
+                        div class="field border label" {
+                            select name="opponent" class="active" {
+                                option value="" { "All" }
+                                @for o in opponents {
+                                    option
+                                        value=(o)
+                                        selected[filter.opponent.as_ref().is_some_and(|f| f.eq_ignore_ascii_case(o))]
+                                    { (o) }
+                                }
+                            }
+                            label for="opponent" class="active" { "Opponent" }
+                        }
+                    }
+                    div class="s12 m6 l2" {
+                        div class="field middle-align" {
+                            button type="submit" class="primary small small-elevate" { "Filter" }
+                        }
+                    }
+                }
+            }
+        }
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    (sort_header(filter, sort, LeaderboardSort::Name, "Name"))
+                    (sort_header(filter, sort, LeaderboardSort::Games, "Games"))
+                    (sort_header(filter, sort, LeaderboardSort::Minutes, "Minutes"))
+                    (sort_header(filter, sort, LeaderboardSort::AverageMinutes, "Avg Minutes"))
+                    (sort_header(filter, sort, LeaderboardSort::Available, "Avail %"))
+                    (sort_header(filter, sort, LeaderboardSort::Mvps, "MVP"))
+                    (sort_header(filter, sort, LeaderboardSort::Awards, "Awards"))
+                    (sort_header(filter, sort, LeaderboardSort::Points, "Points"))
+                    (sort_header(filter, sort, LeaderboardSort::Streak, "Streak"))
+                }
+            }
+            tbody {
+                @for row in rows {
+                    tr {
+                        td { (row.number) }
+                        td { a href={ "/players/" (row.player_id) } { (row.name) } }
+                        td { (row.games) }
+                        td { (row.played()) }
+                        td { (row.average_played()) }
+                        td { (row.available_percentage()) }
+                        td { (row.mvps) }
+                        td { (row.awards) }
+                        td { (row.points) }
+                        td { (row.longest_streak) " (now " (row.current_streak) ")" }
+                    }
+                }
+            }
+        }
+        @if !minutes.game_ids.is_empty() {
+            h3 class="small" { "Minutes" }
+            (minutes_heatmap(minutes))
+            h3 class="small" { "Trends" }
+            (player_trends(minutes))
+        }
+    }
+}
+
+pub fn discipline(season: &str, seasons: &[String], totals: &[DisciplineTotals]) -> Markup {
+    html! {
+        h2 class="small" { "Discipline " (season) }
+        (season_links("/discipline", season, seasons))
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Name" }
+                    th { "Fouls" }
+                    th { "Green" }
+                    th { "Yellow" }
+                    th { "Red" }
+                }
+            }
+            tbody {
+                @for total in totals {
+                    tr {
+                        td { (total.number) }
+                        td { (total.name) }
+                        td { (total.fouls) }
+                        td { (total.green) }
+                        td { (total.yellow) }
+                        td { (total.red) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn plus_minus(
+    season: &str,
+    seasons: &[String],
+    totals: &[PlusMinusTotals],
+    season_lineups: &[Lineup],
+) -> Markup {
+    html! {
+        h2 class="small" { "Plus/Minus " (season) }
+        (season_links("/plus-minus", season, seasons))
+        (plus_minus_table(totals))
+        h3 class="small" { "Best Lineups" }
+        (lineups(season_lineups))
+    }
+}
+
+/// `plus_minus_table` lists each player's season points and plus/minus, with per game averages.
+pub fn plus_minus_table(totals: &[PlusMinusTotals]) -> Markup {
+    html! {
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Name" }
+                    th { "Games" }
+                    th { "Points" }
+                    th { "Avg Points" }
+                    th { "+/-" }
+                    th { "Avg +/-" }
+                }
+            }
+            tbody {
+                @for total in totals {
+                    tr {
+                        td { (total.number) }
+                        td { (total.name) }
+                        td { (total.games) }
+                        td { (total.points) }
+                        td { (total.average_points()) }
+                        td { (format!("{:+}", total.plus_minus)) }
+                        td { (total.average_plus_minus()) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn awards(
+    season: &str,
+    seasons: &[String],
+    categories: &[String],
+    tallies: &[AwardTally],
+) -> Markup {
+    html! {
+        h2 class="small" { "Awards " (season) }
+        (season_links("/awards", season, seasons))
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Name" }
+                    @for category in categories {
+                        th { (category) }
+                    }
+                    th { "Total" }
+                }
+            }
+            tbody {
+                @for tally in tallies {
+                    tr {
+                        td { (tally.number) }
+                        td { (tally.name) }
+                        @for count in &tally.counts {
+                            td { (count) }
+                        }
+                        td { (tally.total()) }
+                    }
+                }
+            }
+        }
+    }
+}
+
+pub fn incidents(season: &str, seasons: &[String], incidents: &[IncidentEntry]) -> Markup {
+    html! {
+        h2 class="small" { "Incidents " (season) }
+        (season_links("/incidents", season, seasons))
+        table class="table" {
+            thead {
+                tr {
+                    th { "Date" }
+                    th { "Game" }
+                    th { "Period" }
+                    th { "Game Clock" }
+                    th { "Player" }
+                    th { "What happened" }
+                    th { "Returned" }
+                }
+            }
+            tbody {
+                @for incident in incidents {
+                    tr {
+                        td { (incident.time.format("%Y-%m-%d %H:%M")) }
+                        td { a href={ "/games/" (incident.game_id) } { (incident.game_id) } }
+                        td { (incident.period) }
+                        td { (incident.game_clock()) }
+                        td { (incident.player) }
+                        td { (incident.description) }
+                        td { @if incident.returned { "yes" } @else { "no" } }
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use super::charts_templates::playing_time;
+use super::games_templates::timeline;
+use crate::game::Team;
+use crate::player::duration;
+use crate::stats::MVP;
+use crate::{GameState, GameView};
+use maud::{Markup, html};
+
+/// `result` is the outcome for our team, an abandoned game having none.
+pub fn result(game: &GameView) -> &'static str {
+    if game.state == GameState::Abandoned {
+        return "Abandoned";
+    }
+    match game.forfeited_by {
+        Some(Team::Them) => return "Win",
+        Some(Team::Us) => return "Loss",
+        None => {}
+    }
+
+    match game.score_us.cmp(&game.score_them) {
+        std::cmp::Ordering::Greater => "Win",
+        std::cmp::Ordering::Less => "Loss",
+        std::cmp::Ordering::Equal => "Draw",
+    }
+}
+
+pub fn title(game: &GameView) -> String {
+    if game.opponent.is_empty() {
+        format!("Game {}", game.id)
+    } else {
+        format!("Game {} vs {}", game.id, game.opponent)
+    }
+}
+
+/// `awards` lists the MVP then each award category given, as (category, player name).
+pub fn awards(game: &GameView) -> Vec<(String, String)> {
+    let mvp = game.mvp.map(|id| (MVP.to_string(), game.player_name(&id)));
+    let awards = game
+        .awards
+        .iter()
+        .map(|award| (award.category.clone(), game.player_name(&award.player_id)));
+
+    mvp.into_iter().chain(awards).collect()
+}
+
+/// `recap` is a plain text Markdown summary of a finished game, for pasting into the team chat.
+pub fn recap(game: &GameView) -> String {
+    let mut lines = vec![format!("**{}**", title(game))];
+    if let Some(st) = game.start_time {
+        lines.push(st.format("%Y-%m-%d").to_string());
+    }
+    lines.push(format!(
+        "{} {} - {}",
+        result(game),
+        game.score_us,
+        game.score_them
+    ));
+    lines.push(format!(
+        "{} periods, {} total",
+        game.periods.len(),
+        game.total_duration()
+    ));
+
+    let awards = awards(game);
+    if !awards.is_empty() {
+        lines.push(String::new());
+        for (category, name) in awards {
+            lines.push(format!("🏆 {}: {}", category, name));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push("Minutes".to_string());
+    for player in &game.players {
+        lines.push(format!(
+            "- #{} {} {} ({} pts)",
+            player.number,
+            player.name,
+            duration(player.played_duration()),
+            player.points
+        ));
+    }
+
+    let notes: Vec<&str> = game
+        .notes
+        .iter()
+        .filter(|n| n.player_id.is_none())
+        .map(|n| n.text.as_str())
+        .collect();
+    if !notes.is_empty() {
+        lines.push(String::new());
+        lines.push("Notes".to_string());
+        for note in notes {
+            lines.push(format!("- {}", note));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// `summary` is the post game page, with a recap to copy for the team chat.
+pub fn summary(game: &GameView) -> Markup {
+    html! {
+        h2 class="small" { (title(game)) }
+        p {
+            (game.start_time_as_digital()) " | "
+            strong { (result(game)) " " (game.score_us) " - " (game.score_them) }
+            " | " (game.total_duration())
+        }
+        a href={ "/games/" (game.id) } {
+            button class="border small" type="button" { "Game" }
+        }
+
+        h3 class="small" { "Recap" }
+        textarea #recap class="border" rows="8" readonly="" style="width: 100%" { (recap(game)) }
+        button
+            class="primary small small-elevate"
+            type="button"
+            onclick="navigator.clipboard.writeText(document.getElementById('recap').value); this.innerText = 'Copied';"
+        { "Copy recap" }
+
+        h3 class="small" { "Periods" }
+        table class="table" {
+            thead {
+                tr {
+                    th { "Period" }
+                    th { "Duration" }
+                }
+            }
+            tbody {
+                @for (i, period) in game.periods.iter().enumerate() {
+                    tr {
+                        td { (i + 1) }
+                        td {
+                            @if let Some(et) = period.end_time {
+                                (duration(et - period.start_time))
+                            } @else { "-" }
+                        }
+                    }
+                }
+            }
+        }
+
+        h3 class="small" { "Minutes" }
+        table class="table" {
+            thead {
+                tr {
+                    th { "#" }
+                    th { "Name" }
+                    th { "Minutes" }
+                    th { "Avail %" }
+                    th { "Subs On" }
+                    th { "Points" }
+                    th { "+/-" }
+                }
+            }
+            tbody {
+                @for player in &game.players {
+                    tr {
+                        td { (player.number) }
+                        td { a href={ "/players/" (player.id) } { (player.name) } }
+                        td { (duration(player.played_duration())) }
+                        td { (player.available_percentage()) }
+                        td { (player.play_count) }
+                        td { (player.points) }
+                        td { (player.plus_minus_signed()) }
+                    }
+                }
+            }
+        }
+        (playing_time(game))
+
+        @let awards = awards(game);
+        @if !awards.is_empty() {
+            h3 class="small" { "Awards" }
+            ul class="list" {
+                @for (category, name) in awards {
+                    li { strong { (category) } " " (name) }
+                }
+            }
+        }
+
+        @if !game.notes.is_empty() {
+            h3 class="small" { "Notes" }
+            ul class="list" {
+                @for note in &game.notes {
+                    li {
+                        @if let Some(player) = &note.player { strong { (player) } " " }
+                        (note.text)
+                    }
+                }
+            }
+        }
+
+        h3 class="small" { "Timeline" }
+        (timeline(&game.timeline))
+    }
+}
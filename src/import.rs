@@ -0,0 +1,340 @@
+//! `import` reads a team roster from CSV, previewing how each row changes the players.
+
+use crate::{Attribute, Error, Player};
+
+/// `RosterRow` is a player read from a roster CSV.
+#[derive(Clone, Debug)]
+pub struct RosterRow {
+    /// 1 based, the header being line 1.
+    pub line: usize,
+    pub name: String,
+    pub number: u32,
+    /// `None` without a position column, keeping an existing player's position.
+    pub position: Option<String>,
+    /// `None` without a tags column, keeping an existing player's tags.
+    pub attributes: Option<Vec<Attribute>>,
+}
+
+/// `RosterStatus` is what importing a `RosterRow` will do.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RosterStatus {
+    New,
+    /// the existing player with the same name and what changes.
+    Updated {
+        player_id: u32,
+        changes: Vec<String>,
+    },
+    /// skipped, with the reason.
+    Duplicate(String),
+}
+
+/// `RosterImport` is a `RosterRow` previewed against the current players.
+#[derive(Clone, Debug)]
+pub struct RosterImport {
+    pub row: RosterRow,
+    pub status: RosterStatus,
+}
+
+// records splits CSV text into records of fields, handling quoted fields containing separators,
+// quotes ("") and line breaks.
+fn records(csv: &str) -> Vec<Vec<String>> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = csv.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (quoted, c) {
+            (true, '"') if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            (true, '"') => quoted = false,
+            (true, c) => field.push(c),
+            (false, '"') => quoted = true,
+            (false, ',') => record.push(std::mem::take(&mut field)),
+            (false, '\r') => {}
+            (false, '\n') => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+            }
+            (false, c) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records
+}
+
+fn parse_tags(tags: &str, line: usize) -> Result<Vec<Attribute>, Error> {
+    let mut attributes = vec![];
+    for tag in tags
+        .split([',', ';', ' '])
+        .map(str::trim)
+        .filter(|t| !t.is_empty())
+    {
+        let attribute = Attribute::ALL
+            .into_iter()
+            .find(|a| a.to_string().eq_ignore_ascii_case(tag))
+            .ok_or_else(|| {
+                let known: Vec<String> = Attribute::ALL.iter().map(|a| a.to_string()).collect();
+                Error::InvalidInput(format!(
+                    "line {}: unknown tag \"{}\", expected one of {}",
+                    line,
+                    tag,
+                    known.join(", ")
+                ))
+            })?;
+        if !attributes.contains(&attribute) {
+            attributes.push(attribute);
+        }
+    }
+
+    Ok(attributes)
+}
+
+/// `parse_roster` reads a roster CSV with a header row naming the "name" and "number" columns,
+/// and optionally "position" and "tags" columns. Blank lines are skipped.
+pub fn parse_roster(csv: &str) -> Result<Vec<RosterRow>, Error> {
+    let mut records = records(csv.trim_start_matches('\u{feff}')).into_iter();
+    let header: Vec<String> = records
+        .next()
+        .unwrap_or_default()
+        .iter()
+        .map(|h| h.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|h| h == name);
+    let (Some(name_column), Some(number_column)) = (column("name"), column("number")) else {
+        return Err(Error::InvalidInput(
+            "roster must have a header row with name and number columns".to_string(),
+        ));
+    };
+    let position_column = column("position");
+    let tags_column = column("tags");
+
+    let mut rows = vec![];
+    for (i, record) in records.enumerate() {
+        let line = i + 2;
+        let value = |column: Option<usize>| {
+            column
+                .and_then(|c| record.get(c))
+                .map(|v| v.trim().to_string())
+                .unwrap_or_default()
+        };
+        if record.iter().all(|f| f.trim().is_empty()) {
+            continue;
+        }
+
+        let name = value(Some(name_column));
+        if name.is_empty() {
+            return Err(Error::InvalidInput(format!("line {}: name is empty", line)));
+        }
+        let number = value(Some(number_column))
+            .parse::<u32>()
+            .map_err(|_| Error::InvalidInput(format!("line {}: number must be a number", line)))?;
+
+        rows.push(RosterRow {
+            line,
+            name,
+            number,
+            position: position_column.map(|c| value(Some(c))),
+            attributes: tags_column
+                .map(|c| parse_tags(&value(Some(c)), line))
+                .transpose()?,
+        });
+    }
+
+    Ok(rows)
+}
+
+fn or_dash(value: &str) -> &str {
+    if value.is_empty() { "-" } else { value }
+}
+
+fn tags(attributes: &[Attribute]) -> String {
+    let tags: Vec<String> = attributes.iter().map(|a| a.to_string()).collect();
+    if tags.is_empty() {
+        "-".to_string()
+    } else {
+        tags.join(" ")
+    }
+}
+
+/// `preview_roster` matches each row to an existing player by name, ignoring case. Rows repeating
+/// an earlier row or matching a player exactly are duplicates. Missing optional columns aren't
+/// changes.
+pub fn preview_roster(rows: Vec<RosterRow>, players: &[Player]) -> Vec<RosterImport> {
+    let mut previews: Vec<RosterImport> = vec![];
+    for row in rows {
+        let earlier = previews
+            .iter()
+            .find(|p| p.row.name.eq_ignore_ascii_case(&row.name));
+        let existing = players
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(&row.name));
+
+        let status = match (earlier, existing) {
+            (Some(earlier), _) => {
+                RosterStatus::Duplicate(format!("repeats line {}", earlier.row.line))
+            }
+            (None, None) => RosterStatus::New,
+            (None, Some(player)) => {
+                let mut changes = vec![];
+                if player.name != row.name {
+                    changes.push(format!("name {} to {}", player.name, row.name));
+                }
+                if player.number != row.number {
+                    changes.push(format!("number {} to {}", player.number, row.number));
+                }
+                if let Some(position) = &row.position
+                    && &player.position != position
+                {
+                    changes.push(format!(
+                        "position {} to {}",
+                        or_dash(&player.position),
+                        or_dash(position)
+                    ));
+                }
+                if let Some(attributes) = &row.attributes
+                    && &player.attributes != attributes
+                {
+                    changes.push(format!(
+                        "tags {} to {}",
+                        tags(&player.attributes),
+                        tags(attributes)
+                    ));
+                }
+
+                if changes.is_empty() {
+                    RosterStatus::Duplicate("matches an existing player".to_string())
+                } else {
+                    RosterStatus::Updated {
+                        player_id: player.id,
+                        changes,
+                    }
+                }
+            }
+        };
+
+        previews.push(RosterImport { row, status });
+    }
+
+    previews
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records_quoted_fields() {
+        let csv = "name,number\r\n\"Smith, Jo\",1\r\n\"Jo \"\"JJ\"\" Lee\",2\r\n\"two\nlines\",3";
+        assert_eq!(
+            records(csv),
+            vec![
+                vec!["name", "number"],
+                vec!["Smith, Jo", "1"],
+                vec!["Jo \"JJ\" Lee", "2"],
+                vec!["two\nlines", "3"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_records_trailing_line() {
+        assert_eq!(records("a,b\n1,2\n"), vec![vec!["a", "b"], vec!["1", "2"]]);
+        assert_eq!(records("a,\n"), vec![vec!["a", ""]]);
+        assert!(records("").is_empty());
+    }
+
+    #[test]
+    fn test_parse_roster() {
+        let csv = "\u{feff}Number,Name,Position,Tags\r\n7,\"Smith, Jo\",guard,tall BEGINNER\r\n\r\n8, Lee ,,\r\n";
+        let rows = parse_roster(csv).unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 2);
+        assert_eq!(rows[0].name, "Smith, Jo");
+        assert_eq!(rows[0].number, 7);
+        assert_eq!(rows[0].position.as_deref(), Some("guard"));
+        assert_eq!(
+            rows[0].attributes,
+            Some(vec![Attribute::Tall, Attribute::Beginner])
+        );
+        // the blank line is skipped but still counted.
+        assert_eq!(rows[1].line, 4);
+        assert_eq!(rows[1].name, "Lee");
+        assert_eq!(rows[1].position.as_deref(), Some(""));
+        assert_eq!(rows[1].attributes, Some(vec![]));
+
+        let rows = parse_roster("name,number\nJo,7").unwrap();
+        assert_eq!(rows[0].position, None);
+        assert_eq!(rows[0].attributes, None);
+    }
+
+    #[test]
+    fn test_parse_roster_errors() {
+        for csv in ["", "name,position\nJo,guard", "Jo,7"] {
+            assert!(
+                matches!(parse_roster(csv), Err(Error::InvalidInput(_))),
+                "{:?}",
+                csv
+            );
+        }
+        for csv in [
+            "name,number\n,7",
+            "name,number\nJo,seven",
+            "name,number,tags\nJo,7,fast",
+        ] {
+            assert!(
+                matches!(parse_roster(csv), Err(Error::InvalidInput(e)) if e.starts_with("line 2:")),
+                "{:?}",
+                csv
+            );
+        }
+    }
+
+    #[test]
+    fn test_preview_roster() {
+        let mut existing = Player::new(1, 7, "Jo".to_string());
+        existing.position = "guard".to_string();
+        let unchanged = Player::new(2, 8, "Lee".to_string());
+        let csv = "name,number,position\n\
+            jo,9,guard\n\
+            Lee,8,\n\
+            Sam,10,\n\
+            SAM,11,\n";
+        let previews = preview_roster(parse_roster(csv).unwrap(), &[existing, unchanged]);
+
+        let statuses: Vec<RosterStatus> = previews.into_iter().map(|p| p.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                RosterStatus::Updated {
+                    player_id: 1,
+                    changes: vec!["name Jo to jo".to_string(), "number 7 to 9".to_string()],
+                },
+                RosterStatus::Duplicate("matches an existing player".to_string()),
+                RosterStatus::New,
+                RosterStatus::Duplicate("repeats line 4".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preview_roster_missing_columns() {
+        let mut existing = Player::new(1, 7, "Jo".to_string());
+        existing.position = "guard".to_string();
+        existing.attributes = vec![Attribute::Tall];
+        let previews = preview_roster(parse_roster("name,number\nJo,7").unwrap(), &[existing]);
+
+        assert_eq!(
+            previews[0].status,
+            RosterStatus::Duplicate("matches an existing player".to_string())
+        );
+    }
+}
@@ -1,8 +1,13 @@
+pub mod dump;
 pub mod error;
+pub mod export;
 pub mod game;
 pub mod http;
+pub mod import;
 pub mod player;
 pub mod repo;
+pub mod settings;
+pub mod stats;
 pub mod svc;
 
 pub use self::error::Error;
@@ -10,8 +15,9 @@ pub use self::game::GameView;
 pub use self::game::{Event, EventError};
 pub use self::game::{Game, GameState, into_game_views};
 pub use self::http::{AxumApp, User};
-pub use self::player::{Player, PlayerView, into_player_views};
+pub use self::player::{Attribute, Player, PlayerView, into_player_views};
 pub use self::repo::{InMemoryRepo, Repo, SqliteRepo};
+pub use self::settings::Settings;
 pub use self::svc::Service;
 
 use clap::Parser;
@@ -36,6 +42,38 @@ struct CLIArgs {
     /// Basic Auth 'user:pass' for HTTP server
     #[arg(short, long)]
     basic_auth: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+/// `Command` is what to run, the HTTP server by default.
+#[derive(clap::Subcommand, Debug, Clone, Default)]
+pub enum Command {
+    /// Run the HTTP server
+    #[default]
+    Serve,
+    /// Recalculate player totals from finished games
+    RebuildTotals,
+    /// Print players as CSV
+    ExportPlayers,
+    /// Print games as CSV
+    ExportGames,
+    /// Print a game's minutes per player and period as CSV
+    ExportMinutes {
+        /// Game number
+        game_id: u32,
+    },
+    /// Print all players, games and settings as JSON
+    ExportJson,
+    /// Load a JSON export
+    ImportJson {
+        /// JSON export file path
+        file: std::path::PathBuf,
+        /// Replace all players and games, or merge in those not already present
+        #[arg(short, long, value_enum, default_value = "merge")]
+        mode: dump::ImportMode,
+    },
 }
 
 pub enum RepoConfig {
@@ -50,6 +88,7 @@ impl Default for RepoConfig {
 }
 
 pub struct Config {
+    command: Command,
     repo_config: RepoConfig,
     listen_addr: String,
     basic_auth: Option<User>,
@@ -58,6 +97,7 @@ pub struct Config {
 impl Default for Config {
     fn default() -> Self {
         Self {
+            command: Command::default(),
             listen_addr: String::from(LISTEN_ADDR),
             repo_config: RepoConfig::default(),
             basic_auth: None,
@@ -72,6 +112,7 @@ impl Config {
         let args = CLIArgs::try_parse_from(args)
             .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
 
+        cfg.command = args.command.unwrap_or_default();
         cfg.repo_config = RepoConfig::Sqlite(args.sqlite_filepath);
 
         if !args.listen_addr.is_empty() {
@@ -133,6 +174,38 @@ pub async fn run(cfg: Config) -> Result<(), std::io::Error> {
         }
     };
     let svc = Service::new(repo);
+
+    match cfg.command {
+        Command::Serve => {}
+        Command::RebuildTotals => {
+            let players = svc.rebuild_player_totals()?;
+            println!("rebuilt totals for {} players", players.len());
+            return Ok(());
+        }
+        Command::ExportPlayers => {
+            print!("{}", svc.export_players_csv()?);
+            return Ok(());
+        }
+        Command::ExportGames => {
+            print!("{}", svc.export_games_csv()?);
+            return Ok(());
+        }
+        Command::ExportMinutes { game_id } => {
+            print!("{}", svc.export_game_minutes_csv(&game_id)?);
+            return Ok(());
+        }
+        Command::ExportJson => {
+            println!("{}", svc.export_dump()?.to_json()?);
+            return Ok(());
+        }
+        Command::ImportJson { file, mode } => {
+            let json = std::fs::read_to_string(file)?;
+            let summary = svc.import_dump(dump::Dump::parse(&json)?, mode)?;
+            println!("{}", summary);
+            return Ok(());
+        }
+    }
+
     let app = AxumApp::new(cfg.listen_addr, cfg.basic_auth, svc);
 
     tracing::info!(message = "starting server");
@@ -1,6 +1,46 @@
 use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
 
+/// `Attribute` tags a `Player` with a trait that matters when composing a lineup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Attribute {
+    BallHandler,
+    Tall,
+    Beginner,
+    GoalkeeperCapable,
+}
+
+impl Attribute {
+    pub const ALL: [Attribute; 4] = [
+        Attribute::BallHandler,
+        Attribute::Tall,
+        Attribute::Beginner,
+        Attribute::GoalkeeperCapable,
+    ];
+}
+
+impl std::fmt::Display for Attribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BallHandler => write!(f, "ball-handler"),
+            Self::Tall => write!(f, "tall"),
+            Self::Beginner => write!(f, "beginner"),
+            Self::GoalkeeperCapable => write!(f, "goalkeeper-capable"),
+        }
+    }
+}
+
+/// `Window` is a span of time between two optional bounds, `None` meaning unbounded.
+pub type Window = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// `Stint` is a completed spell on court.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Stint {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+}
+
 /// `Player` represents each participant in the team being managed.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Player {
@@ -10,6 +50,20 @@ pub struct Player {
     pub play_count: u32,
     pub play_start_time: Option<DateTime<Utc>>,
     pub play_duration: Duration,
+    #[serde(default)]
+    pub attributes: Vec<Attribute>,
+    /// free text, eg: "guard" or "goalkeeper".
+    #[serde(default)]
+    pub position: String,
+    // game only, availability window for late arrivals and early departures.
+    #[serde(default)]
+    pub late: bool,
+    #[serde(default)]
+    pub arrived_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub departed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub stints: Vec<Stint>,
 }
 
 impl Player {
@@ -21,13 +75,61 @@ impl Player {
             play_count: 0,
             play_start_time: None,
             play_duration: Duration::zero(),
+            attributes: vec![],
+            position: String::new(),
+            late: false,
+            arrived_at: None,
+            departed_at: None,
+            stints: vec![],
         }
     }
 
+    pub fn has_attribute(&self, attribute: &Attribute) -> bool {
+        self.attributes.contains(attribute)
+    }
+
     pub fn is_playing(&self) -> bool {
         self.play_start_time.is_some()
     }
 
+    /// `is_available` is false while waiting for a late player to arrive or once they have left.
+    pub fn is_available(&self) -> bool {
+        !self.late && self.departed_at.is_none()
+    }
+
+    /// `availability` returns the window the player was at the game, `None` bounds meaning from
+    /// the start or until the end. A late player that never arrived has no window.
+    pub fn availability(&self) -> Option<Window> {
+        if self.late {
+            return None;
+        }
+
+        Some((self.arrived_at, self.departed_at))
+    }
+
+    pub fn mark_late(&mut self) {
+        self.late = true;
+        self.arrived_at = None;
+        self.departed_at = None;
+    }
+
+    /// `arrive` marks a late or departed player as at the game from now.
+    pub fn arrive(&mut self) {
+        // can't arrive while already here, it would reset when they arrived.
+        if self.is_available() {
+            return;
+        }
+
+        self.late = false;
+        self.arrived_at = Some(Utc::now());
+        self.departed_at = None;
+    }
+
+    pub fn depart(&mut self) {
+        self.sub_off();
+        self.departed_at = Some(Utc::now());
+    }
+
     pub fn sub_on(&mut self) {
         // can't sub on someone already on court
         if self.is_playing() {
@@ -45,12 +147,47 @@ impl Player {
         }
 
         if let Some(st) = self.play_start_time {
-            self.play_duration += Utc::now() - st;
+            let now = Utc::now();
+            self.play_duration += now - st;
+            self.stints.push(Stint {
+                start_time: st,
+                end_time: now,
+            });
         }
 
         self.play_start_time = None;
     }
 
+    /// `rests` returns each spell between stints while available, the last spell being open
+    /// ended when the player is on the bench now.
+    pub fn rests(&self) -> Vec<Window> {
+        let Some((from, until)) = self.availability() else {
+            return vec![];
+        };
+
+        let mut rests = vec![];
+        let mut rest_start = from;
+        for stint in &self.stints {
+            rests.push((rest_start, Some(stint.start_time)));
+            rest_start = Some(stint.end_time);
+        }
+
+        match self.play_start_time {
+            Some(st) => rests.push((rest_start, Some(st))),
+            None => rests.push((rest_start, until)),
+        }
+
+        rests
+    }
+
+    /// `on_court_at` is true when the player was, or still is, on court at `time`.
+    pub fn on_court_at(&self, time: DateTime<Utc>) -> bool {
+        self.stints
+            .iter()
+            .any(|s| s.start_time <= time && time < s.end_time)
+            || self.play_start_time.is_some_and(|st| st <= time)
+    }
+
     pub fn add_stats(&mut self, play_count: u32, play_duration: Duration) {
         self.play_count += play_count;
         self.play_duration += play_duration;
@@ -58,12 +195,80 @@ impl Player {
 
     pub fn reset_stats(&self) -> Self {
         Self {
-            id: self.id,
-            name: self.name.clone(),
-            number: self.number,
-            play_count: 0,
-            play_duration: Duration::zero(),
-            play_start_time: None,
+            attributes: self.attributes.clone(),
+            position: self.position.clone(),
+            ..Self::new(self.id, self.number, self.name.clone())
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn at(minutes: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 10, minutes, 0).unwrap()
+    }
+
+    fn stint(start: u32, end: u32) -> Stint {
+        Stint {
+            start_time: at(start),
+            end_time: at(end),
+        }
+    }
+
+    #[test]
+    fn test_rests() {
+        let mut player = Player::new(1, 1, "1".to_string());
+        assert_eq!(player.rests(), vec![(None, None)]);
+
+        player.stints = vec![stint(0, 5), stint(10, 20)];
+        assert_eq!(
+            player.rests(),
+            vec![
+                (None, Some(at(0))),
+                (Some(at(5)), Some(at(10))),
+                (Some(at(20)), None),
+            ]
+        );
+
+        // on court now, so the last rest ended when they went back on.
+        player.play_start_time = Some(at(30));
+        assert_eq!(player.rests().last(), Some(&(Some(at(20)), Some(at(30)))));
+    }
+
+    #[test]
+    fn test_rests_within_availability() {
+        let mut player = Player::new(1, 1, "1".to_string());
+        player.stints = vec![stint(10, 20)];
+        player.arrived_at = Some(at(5));
+        player.departed_at = Some(at(40));
+        assert_eq!(
+            player.rests(),
+            vec![(Some(at(5)), Some(at(10))), (Some(at(20)), Some(at(40)))]
+        );
+
+        player.mark_late();
+        assert_eq!(player.rests(), vec![]);
+    }
+
+    #[test]
+    fn test_arrive_only_when_away() {
+        let mut player = Player::new(1, 1, "1".to_string());
+        player.arrived_at = Some(at(5));
+        player.arrive();
+        assert_eq!(player.arrived_at, Some(at(5)));
+
+        player.mark_late();
+        player.arrive();
+        assert!(player.is_available());
+        assert!(player.arrived_at.is_some_and(|a| a > at(5)));
+
+        player.depart();
+        assert!(!player.is_available());
+        player.arrive();
+        assert!(player.is_available());
+        assert_eq!(player.departed_at, None);
+    }
+}
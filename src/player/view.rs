@@ -1,7 +1,10 @@
-use super::core::Player;
+use super::core::{Attribute, Player, Stint};
+use crate::game::CardKind;
 use chrono::{DateTime, Duration, TimeDelta, Utc};
 
-fn duration(delta: TimeDelta) -> String {
+/// `duration` formats a time span as minutes and seconds, eg: "12m 5s", for every view of playing
+/// time.
+pub fn duration(delta: TimeDelta) -> String {
     let total_seconds = delta.num_seconds();
     let minutes = total_seconds / 60;
     let seconds = total_seconds % 60;
@@ -19,9 +22,36 @@ pub struct PlayerView {
     pub playing: bool,
     pub play_start_time: Option<DateTime<Utc>>,
     pub play_duration: Duration,
+    pub attributes: Vec<Attribute>,
+    pub position: String,
+    pub available: bool,
+    pub late: bool,
+    pub arrived_at: Option<DateTime<Utc>>,
+    pub departed_at: Option<DateTime<Utc>>,
+    pub stints: Vec<Stint>,
+    // game periods are required to calculate below, see `GameView`.
+    pub available_duration: Duration,
+    pub bench_duration: Duration,
+    pub longest_rest: Duration,
+    pub current_rest: Option<Duration>,
+    pub rest_warning: bool,
+    pub fouls: u32,
+    pub fouled_out: bool,
+    pub foul_trouble: bool,
+    pub cards: Vec<CardKind>,
+    /// sin bin time remaining.
+    pub suspension: Option<Duration>,
+    pub sent_off: bool,
+    pub points: u32,
+    /// points for less points against while on court.
+    pub plus_minus: i64,
 }
 
 impl PlayerView {
+    pub fn plus_minus_signed(&self) -> String {
+        format!("{:+}", self.plus_minus)
+    }
+
     pub fn total_duration(&self) -> String {
         duration(self.play_duration)
     }
@@ -33,6 +63,53 @@ impl PlayerView {
 
         "-".to_string()
     }
+
+    /// `can_sub_on` is false while unavailable, fouled out, sent off or in the sin bin.
+    pub fn can_sub_on(&self) -> bool {
+        self.available && !self.fouled_out && !self.sent_off && self.suspension.is_none()
+    }
+
+    /// `played_duration` is the total time on court, including the current stint.
+    pub fn played_duration(&self) -> Duration {
+        self.play_duration
+            + self
+                .play_start_time
+                .map_or(Duration::zero(), |st| Utc::now() - st)
+    }
+
+    /// `available_ratio` is the share of the time the player was at the game that they spent on
+    /// court.
+    pub fn available_ratio(&self) -> Option<f64> {
+        let available = self.available_duration.num_milliseconds();
+        (available > 0).then(|| self.played_duration().num_milliseconds() as f64 / available as f64)
+    }
+
+    pub fn bench_duration(&self) -> String {
+        duration(self.bench_duration)
+    }
+
+    pub fn longest_rest(&self) -> String {
+        duration(self.longest_rest)
+    }
+
+    pub fn current_rest(&self) -> String {
+        self.current_rest
+            .map(duration)
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    /// `suspension_countdown` shows the sin bin time remaining as a clock, eg: "1:05".
+    pub fn suspension_countdown(&self) -> String {
+        self.suspension
+            .map(|s| format!("{}:{:02}", s.num_seconds() / 60, s.num_seconds() % 60))
+            .unwrap_or_else(|| "-".to_string())
+    }
+
+    pub fn available_percentage(&self) -> String {
+        self.available_ratio()
+            .map(|r| format!("{:.0}%", r * 100.0))
+            .unwrap_or_else(|| "-".to_string())
+    }
 }
 
 /// `into_player_views` is a helper function to simplify converting a vector of Player's into
@@ -58,6 +135,26 @@ impl From<&Player> for PlayerView {
             play_duration: player.play_duration,
             play_start_time: player.play_start_time,
             playing: player.is_playing(),
+            attributes: player.attributes.clone(),
+            position: player.position.clone(),
+            available: player.is_available(),
+            late: player.late,
+            arrived_at: player.arrived_at,
+            departed_at: player.departed_at,
+            stints: player.stints.clone(),
+            available_duration: Duration::zero(),
+            bench_duration: Duration::zero(),
+            longest_rest: Duration::zero(),
+            current_rest: None,
+            rest_warning: false,
+            fouls: 0,
+            fouled_out: false,
+            foul_trouble: false,
+            cards: vec![],
+            suspension: None,
+            sent_off: false,
+            points: 0,
+            plus_minus: 0,
         }
     }
 }
@@ -4,6 +4,8 @@
 use crate::Error;
 use crate::Game;
 use crate::Player;
+use crate::Settings;
+use crate::game::Vote;
 
 /// `Repo` describes the methods required for a Service repository.
 pub trait Repo: Send + Sync {
@@ -37,16 +39,30 @@ pub trait Repo: Send + Sync {
     /// `Error` will be returned when a value can't be found or there was an
     /// internal error processing the request.
     fn delete_player(&self, player_id: &u32) -> Result<(), Error>;
+    /// `import_players` creates the `created` players, new ids being assigned, and updates the
+    /// roster details (name, number, attributes and position) of the `updated` players, all or
+    /// nothing.
+    ///
+    /// # Errors
+    ///
+    /// `Error` will be returned when an updated player can't be found or there was an
+    /// internal error processing the request.
+    fn import_players(
+        &self,
+        created: Vec<Player>,
+        updated: Vec<Player>,
+    ) -> Result<Vec<Player>, Error>;
     /// # Errors
     ///
     /// `Error` will be returned when a value can't be found or there was an
     /// internal error processing the request.
-    fn count_games(&self) -> Result<usize, Error>;
+    fn next_game_id(&self) -> Result<u32, Error>;
     /// # Errors
     ///
     /// `Error` will be returned when a value can't be found or there was an
     /// internal error processing the request.
     fn list_games(&self) -> Result<Vec<Game>, Error>;
+    /// `create_game` saves the game, `Data::touch`ing it first.
     /// # Errors
     ///
     /// `Error` will be returned when a value can't be found or there was an
@@ -57,6 +73,7 @@ pub trait Repo: Send + Sync {
     /// `Error` will be returned when a value can't be found or there was an
     /// internal error processing the request.
     fn get_game(&self, game_id: &u32) -> Result<Game, Error>;
+    /// `update_game` saves the game, `Data::touch`ing it first.
     /// # Errors
     ///
     /// `Error` will be returned when a value can't be found or there was an
@@ -67,4 +84,46 @@ pub trait Repo: Send + Sync {
     /// `Error` will be returned when a value can't be found or there was an
     /// internal error processing the request.
     fn delete_game(&self, game_id: &u32) -> Result<(), Error>;
+    /// `cast_vote` records a player of the match vote, replacing the device's earlier vote. Votes
+    /// are stored apart from the game, `get_game` and `list_games` returning them in
+    /// `Voting::votes` and `update_game` ignoring them, so voters can't overwrite the game.
+    ///
+    /// # Errors
+    ///
+    /// `Error` will be returned when the game can't be found or there was an
+    /// internal error processing the request.
+    fn cast_vote(&self, game_id: &u32, vote: Vote) -> Result<(), Error>;
+    /// # Errors
+    ///
+    /// `Error` will be returned when there was an internal error processing the request.
+    fn get_settings(&self) -> Result<Settings, Error>;
+    /// # Errors
+    ///
+    /// `Error` will be returned when there was an internal error processing the request.
+    fn update_settings(&self, settings: Settings) -> Result<(), Error>;
+    /// `get_calendar_token` returns the secret for subscribing to the calendar feed, empty when
+    /// the feed is disabled. It's kept apart from `Settings` as those are copied into every game.
+    ///
+    /// # Errors
+    ///
+    /// `Error` will be returned when there was an internal error processing the request.
+    fn get_calendar_token(&self) -> Result<String, Error>;
+    /// # Errors
+    ///
+    /// `Error` will be returned when there was an internal error processing the request.
+    fn update_calendar_token(&self, token: &str) -> Result<(), Error>;
+    /// `restore` loads players and games keeping their ids, and optionally the settings, all or
+    /// nothing. With `replace` the existing players and games are deleted first.
+    ///
+    /// # Errors
+    ///
+    /// `Error::Conflict` will be returned when a player or game id is already used, or `Error`
+    /// when there was an internal error processing the request.
+    fn restore(
+        &self,
+        settings: Option<Settings>,
+        players: Vec<Player>,
+        games: Vec<Game>,
+        replace: bool,
+    ) -> Result<(), Error>;
 }
@@ -2,6 +2,8 @@ use super::Repo;
 use crate::Error;
 use crate::Game;
 use crate::Player;
+use crate::Settings;
+use crate::game::Vote;
 
 use std::cmp::Reverse;
 use std::{
@@ -9,10 +11,18 @@ use std::{
     sync::{Arc, RwLock},
 };
 
+// next_id returns the id after the highest in `store`, so ids of deleted values aren't reused.
+fn next_id<T>(store: &HashMap<u32, T>) -> u32 {
+    store.keys().max().map_or(0, |id| id + 1)
+}
+
 #[derive(Default)]
 pub struct InMemoryRepo {
     games: Arc<RwLock<HashMap<u32, Game>>>,     // TODO: Arc<Game>
     players: Arc<RwLock<HashMap<u32, Player>>>, // TODO: Arc<Player>
+    settings: Arc<RwLock<Settings>>,
+    calendar_token: Arc<RwLock<String>>,
+    votes: Arc<RwLock<HashMap<u32, Vec<Vote>>>>, // by game id, apart so voters can't overwrite games.
 }
 
 /// `InMemoryRepo` provides an in-memory `Repo` implementation using hash map for storage and
@@ -24,6 +34,19 @@ impl InMemoryRepo {
     pub fn new() -> Self {
         Self::default()
     }
+
+    // with_votes returns a copy of the game with its stored votes.
+    fn with_votes(&self, game: &Game) -> Result<Game, Error> {
+        let votes = self
+            .votes
+            .read()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        let mut game = game.clone();
+        game.set_votes(votes.get(&game.id).cloned().unwrap_or_default());
+
+        Ok(game)
+    }
 }
 
 impl Repo for InMemoryRepo {
@@ -54,12 +77,7 @@ impl Repo for InMemoryRepo {
             .write()
             .map_err(|e| Error::Internal(e.to_string()))?;
 
-        let id: u32 = u32::try_from(store.len()).map_err(|e| Error::Internal(e.to_string()))?;
-
-        if store.contains_key(&id) {
-            return Err(Error::Conflict);
-        }
-
+        let id = next_id(&store);
         let player = Player::new(id, number, name);
 
         _ = store.insert(player.id, player.clone());
@@ -85,11 +103,11 @@ impl Repo for InMemoryRepo {
                 .write()
                 .map_err(|e| Error::Internal(e.to_string()))?;
 
-            if !store.contains_key(&player.number) {
+            if !store.contains_key(&player.id) {
                 return Err(Error::NotFound);
             }
 
-            _ = store.insert(player.number, player);
+            _ = store.insert(player.id, player);
         }
 
         Ok(())
@@ -106,13 +124,52 @@ impl Repo for InMemoryRepo {
             .map_or_else(|| Err(Error::NotFound), |_p| Ok(()))
     }
 
-    fn count_games(&self) -> Result<usize, Error> {
+    fn import_players(
+        &self,
+        created: Vec<Player>,
+        updated: Vec<Player>,
+    ) -> Result<Vec<Player>, Error> {
+        let mut store = self
+            .players
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        // give out the new ids and check the updated players exist before changing anything, so
+        // the import is all or nothing.
+        let created: Vec<Player> = created
+            .into_iter()
+            .zip(next_id(&store)..)
+            .map(|(player, id)| Player { id, ..player })
+            .collect();
+        if updated.iter().any(|p| !store.contains_key(&p.id)) {
+            return Err(Error::NotFound);
+        }
+
+        let mut players = vec![];
+        for player in created {
+            _ = store.insert(player.id, player.clone());
+            players.push(player);
+        }
+        for player in updated {
+            if let Some(existing) = store.get_mut(&player.id) {
+                existing.name = player.name.clone();
+                existing.number = player.number;
+                existing.attributes = player.attributes.clone();
+                existing.position = player.position.clone();
+            }
+            players.push(player);
+        }
+
+        Ok(players)
+    }
+
+    fn next_game_id(&self) -> Result<u32, Error> {
         let store = self
             .games
             .read()
             .map_err(|e| Error::Internal(e.to_string()))?;
 
-        Ok(store.len())
+        Ok(next_id(&store).max(1)) // game ids start at 1, like SQLite.
     }
 
     fn list_games(&self) -> Result<Vec<Game>, Error> {
@@ -121,12 +178,17 @@ impl Repo for InMemoryRepo {
             .read()
             .map_err(|e| Error::Internal(e.to_string()))?;
 
-        let mut games: Vec<Game> = store.values().cloned().collect();
+        let mut games: Vec<Game> = store
+            .values()
+            .map(|g| self.with_votes(g))
+            .collect::<Result<Vec<Game>, _>>()?;
         games.sort_by_key(|g| Reverse(g.id)); // Descending (newest).
         Ok(games)
     }
 
-    fn create_game(&self, game: Game) -> Result<(), Error> {
+    fn create_game(&self, mut game: Game) -> Result<(), Error> {
+        _ = game.take_votes(); // votes are only cast via `cast_vote`.
+        game.shared.touch();
         {
             let mut store = self
                 .games
@@ -151,10 +213,12 @@ impl Repo for InMemoryRepo {
 
         store
             .get(game_id)
-            .map_or_else(|| Err(Error::NotFound), |g| Ok(g.clone()))
+            .map_or_else(|| Err(Error::NotFound), |g| self.with_votes(g))
     }
 
-    fn update_game(&self, game: Game) -> Result<(), Error> {
+    fn update_game(&self, mut game: Game) -> Result<(), Error> {
+        _ = game.take_votes(); // the stored votes may be newer.
+        game.shared.touch();
         {
             let mut store = self
                 .games
@@ -179,6 +243,159 @@ impl Repo for InMemoryRepo {
 
         store
             .remove(game_id)
-            .map_or_else(|| Err(Error::NotFound), |_g| Ok(()))
+            .map_or_else(|| Err(Error::NotFound), |_g| Ok(()))?;
+
+        let mut votes = self
+            .votes
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        _ = votes.remove(game_id);
+
+        Ok(())
+    }
+
+    fn cast_vote(&self, game_id: &u32, vote: Vote) -> Result<(), Error> {
+        let store = self
+            .games
+            .read()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        if !store.contains_key(game_id) {
+            return Err(Error::NotFound);
+        }
+
+        let mut votes = self
+            .votes
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let game_votes = votes.entry(*game_id).or_default();
+        game_votes.retain(|v| v.device_id != vote.device_id);
+        game_votes.push(vote);
+
+        Ok(())
+    }
+
+    fn get_settings(&self) -> Result<Settings, Error> {
+        let store = self
+            .settings
+            .read()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        Ok(store.clone())
+    }
+
+    fn update_settings(&self, settings: Settings) -> Result<(), Error> {
+        let mut store = self
+            .settings
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        *store = settings;
+
+        Ok(())
+    }
+
+    fn get_calendar_token(&self) -> Result<String, Error> {
+        let store = self
+            .calendar_token
+            .read()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        Ok(store.clone())
+    }
+
+    fn update_calendar_token(&self, token: &str) -> Result<(), Error> {
+        let mut store = self
+            .calendar_token
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        *store = token.to_string();
+
+        Ok(())
+    }
+
+    fn restore(
+        &self,
+        settings: Option<Settings>,
+        players: Vec<Player>,
+        games: Vec<Game>,
+        replace: bool,
+    ) -> Result<(), Error> {
+        let mut player_store = self
+            .players
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+        let mut game_store = self
+            .games
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        // check before changing anything, so the restore is all or nothing.
+        if !replace
+            && (players.iter().any(|p| player_store.contains_key(&p.id))
+                || games.iter().any(|g| game_store.contains_key(&g.id)))
+        {
+            return Err(Error::Conflict);
+        }
+
+        let mut vote_store = self
+            .votes
+            .write()
+            .map_err(|e| Error::Internal(e.to_string()))?;
+
+        if replace {
+            player_store.clear();
+            game_store.clear();
+            vote_store.clear();
+        }
+        for player in players {
+            _ = player_store.insert(player.id, player);
+        }
+        for mut game in games {
+            let votes = game.take_votes();
+            if !votes.is_empty() {
+                _ = vote_store.insert(game.id, votes);
+            }
+            _ = game_store.insert(game.id, game);
+        }
+
+        if let Some(settings) = settings {
+            let mut store = self
+                .settings
+                .write()
+                .map_err(|e| Error::Internal(e.to_string()))?;
+            *store = settings;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_update_player_by_id() {
+        let repo = InMemoryRepo::new();
+        let first = repo.create_player(4, "Alex".to_string()).unwrap();
+        let second = repo.create_player(7, "Sam".to_string()).unwrap();
+
+        // renumbering and renaming a player keeps them under the same id.
+        let mut updated = first.clone();
+        updated.number = 9;
+        updated.name = "Alexis".to_string();
+        repo.update_player(updated).unwrap();
+
+        let players = repo.list_players().unwrap();
+        assert_eq!(players.len(), 2);
+        let player = repo.get_player(&first.id).unwrap();
+        assert_eq!((player.number, player.name.as_str()), (9, "Alexis"));
+        let player = repo.get_player(&second.id).unwrap();
+        assert_eq!((player.number, player.name.as_str()), (7, "Sam"));
+
+        let missing = Player::new(second.id + 1, 1, "Jo".to_string());
+        assert!(matches!(repo.update_player(missing), Err(Error::NotFound)));
+        assert_eq!(repo.list_players().unwrap().len(), 2);
     }
 }
@@ -1,11 +1,13 @@
-use chrono::Duration;
+use chrono::{Datelike, Duration, Utc};
 use rusqlite::Connection;
 
 use super::Repo;
 use crate::Error;
 use crate::Player;
-use crate::game::{Data, Game, State};
+use crate::Settings;
+use crate::game::{Data, Game, State, Vote};
 
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex, MutexGuard};
 
 // Convert from an owned `Game`, avoiding clone in caller.
@@ -22,6 +24,14 @@ impl From<rusqlite::Error> for Error {
     }
 }
 
+// conflict maps a duplicate primary key to `Error::Conflict`.
+fn conflict(re: rusqlite::Error) -> Error {
+    match re.sqlite_error_code() {
+        Some(rusqlite::ErrorCode::ConstraintViolation) => Error::Conflict,
+        _ => Error::from(re),
+    }
+}
+
 pub struct SqliteRepo {
     conn: Arc<Mutex<Connection>>,
 }
@@ -34,10 +44,12 @@ struct GameSqlRow {
     state_json: String,
 }
 
+// votes are stored in their own table, so they're left out of the game row.
 impl TryFrom<Game> for GameSqlRow {
     type Error = Error;
 
-    fn try_from(game: Game) -> Result<Self, Self::Error> {
+    fn try_from(mut game: Game) -> Result<Self, Self::Error> {
+        _ = game.take_votes();
         let shared_json =
             serde_json::to_string(&game.shared).map_err(|e| Error::Internal(e.to_string()))?;
         let state_json =
@@ -76,6 +88,8 @@ struct PlayerSqlRow {
     play_count: u32,
     play_start_time: Option<i64>,
     play_duration: Option<i64>,
+    attributes_json: String,
+    position: String,
 }
 
 impl From<PlayerSqlRow> for Player {
@@ -85,14 +99,15 @@ impl From<PlayerSqlRow> for Player {
         });
 
         Player {
-            id: row.id,
-            name: row.name,
-            number: row.number,
             play_count: row.play_count,
             play_start_time: row
                 .play_start_time
                 .map_or_else(|| None, chrono::DateTime::from_timestamp_millis),
             play_duration: pd,
+            // tolerate hand edited rows, attributes are only used for lineup warnings.
+            attributes: serde_json::from_str(&row.attributes_json).unwrap_or_default(),
+            position: row.position,
+            ..Player::new(row.id, row.number, row.name)
         }
     }
 }
@@ -107,10 +122,142 @@ impl From<Player> for PlayerSqlRow {
             play_count: player.play_count,
             play_start_time: pst,
             play_duration: Some(player.play_duration.num_milliseconds()),
+            attributes_json: serde_json::to_string(&player.attributes)
+                .unwrap_or_else(|_| "[]".to_string()),
+            position: player.position,
         }
     }
 }
 
+// add_column_if_missing migrates tables created by earlier versions, as sqlite has no
+// `ADD COLUMN IF NOT EXISTS`.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), Error> {
+    let exists: bool = conn.query_row(
+        &format!("SELECT count(*) > 0 FROM pragma_table_info('{table}') WHERE name = ?1"),
+        [column],
+        |row| row.get(0),
+    )?;
+
+    if !exists {
+        conn.execute_batch(&format!(
+            "ALTER TABLE {table} ADD COLUMN {column} {definition};"
+        ))?;
+    }
+
+    Ok(())
+}
+
+// select_votes returns the votes for a game, or every game when `game_id` is `None`, by game id.
+fn select_votes(
+    conn: &Connection,
+    game_id: Option<&u32>,
+) -> Result<HashMap<u32, Vec<Vote>>, Error> {
+    let mut stmt = conn.prepare(
+        "
+        SELECT
+            game_id,
+            device_id,
+            player_id,
+            time
+        FROM
+            vote
+        WHERE
+            ?1 IS NULL OR game_id = ?1
+        ORDER BY
+            time ASC
+        ",
+    )?;
+
+    let mut votes: HashMap<u32, Vec<Vote>> = HashMap::new();
+    let rows = stmt.query_map([game_id], |row| {
+        let time: i64 = row.get(3)?;
+        Ok((
+            row.get::<_, u32>(0)?,
+            Vote {
+                device_id: row.get(1)?,
+                player_id: row.get(2)?,
+                time: chrono::DateTime::from_timestamp_millis(time).unwrap_or_default(),
+            },
+        ))
+    })?;
+    for row in rows {
+        let (game_id, vote) = row?;
+        votes.entry(game_id).or_default().push(vote);
+    }
+
+    Ok(votes)
+}
+
+// insert_votes stores a game's votes, replacing each device's earlier vote.
+fn insert_votes(conn: &Connection, game_id: &u32, votes: &[Vote]) -> Result<(), Error> {
+    let mut stmt = conn.prepare(
+        "
+        INSERT INTO
+            vote
+            (game_id, device_id, player_id, time)
+        VALUES
+            (?1, ?2, ?3, ?4)
+        ON CONFLICT(game_id, device_id) DO UPDATE SET
+            player_id = excluded.player_id,
+            time = excluded.time
+        ",
+    )?;
+    for vote in votes {
+        stmt.execute((
+            game_id,
+            &vote.device_id,
+            vote.player_id,
+            vote.time.timestamp_millis(),
+        ))?;
+    }
+
+    Ok(())
+}
+
+// migrate_seasons gives settings and games saved before seasons were added a season. Games get the
+// year they were played or scheduled in, so they stay in that season when the year changes.
+fn migrate_seasons(conn: &Connection) -> Result<(), Error> {
+    let year = Utc::now().year().to_string();
+    _ = conn.execute(
+        "UPDATE settings SET data = json_set(data, '$.season', ?1)
+        WHERE json_extract(data, '$.season') IS NULL",
+        [&year],
+    )?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, shared, state FROM game WHERE json_extract(shared, '$.settings.season') IS NULL",
+    )?;
+    let games = stmt
+        .query_map([], |row| {
+            Ok(GameSqlRow {
+                id: row.get(0)?,
+                shared_json: row.get(1)?,
+                state_json: row.get(2)?,
+            })
+        })?
+        .map(|row| Game::try_from(row?))
+        .collect::<Result<Vec<Game>, _>>()?;
+    for mut game in games {
+        game.shared.settings.season = game
+            .state
+            .start_time()
+            .or(game.shared.scheduled_at)
+            .map_or_else(|| year.clone(), |t| t.year().to_string());
+        let row = GameSqlRow::try_from(game)?;
+        _ = conn.execute(
+            "UPDATE game SET shared = ?2 WHERE id = ?1",
+            (&row.id, &row.shared_json),
+        )?;
+    }
+
+    Ok(())
+}
+
 /// `SqliteRepo` provides a sqlite `Repo` implementation.
 impl SqliteRepo {
     fn get_conn(&self) -> Result<MutexGuard<'_, Connection>, Error> {
@@ -140,9 +287,31 @@ impl SqliteRepo {
                 play_start_time INTEGER, -- unix timestamp milliseconds
                 play_duration   INTEGER NOT NULL DEFAULT 0 -- milliseconds
             );
+
+            CREATE TABLE IF NOT EXISTS settings (
+                id   INTEGER PRIMARY KEY CHECK (id = 1), -- single row
+                data TEXT NOT NULL
+            );
+
+            CREATE TABLE IF NOT EXISTS vote (
+                game_id   INTEGER NOT NULL,
+                device_id TEXT NOT NULL,
+                player_id INTEGER NOT NULL,
+                time      INTEGER NOT NULL, -- unix timestamp milliseconds
+                PRIMARY KEY (game_id, device_id)
+            );
+
+            CREATE TABLE IF NOT EXISTS calendar (
+                id    INTEGER PRIMARY KEY CHECK (id = 1), -- single row
+                token TEXT NOT NULL
+            );
             ",
         )?;
 
+        add_column_if_missing(&conn, "player", "attributes", "TEXT NOT NULL DEFAULT '[]'")?;
+        add_column_if_missing(&conn, "player", "position", "TEXT NOT NULL DEFAULT ''")?;
+        migrate_seasons(&conn)?;
+
         Ok(Self {
             conn: Arc::new(Mutex::new(conn)),
         })
@@ -171,7 +340,9 @@ impl Repo for SqliteRepo {
                 number,
                 play_count,
                 play_start_time,
-                play_duration
+                play_duration,
+                attributes,
+                position
             FROM
                 player
             ORDER BY
@@ -188,6 +359,8 @@ impl Repo for SqliteRepo {
                     play_count: row.get(3)?,
                     play_start_time: row.get(4)?,
                     play_duration: row.get(5)?,
+                    attributes_json: row.get(6)?,
+                    position: row.get(7)?,
                 };
 
                 Ok(Player::from(sql_row))
@@ -222,6 +395,8 @@ impl Repo for SqliteRepo {
                     play_count: 0,
                     play_start_time: None,
                     play_duration: None,
+                    attributes_json: "[]".to_string(),
+                    position: String::new(),
                 };
 
                 Ok(Player::from(sql_row))
@@ -242,7 +417,9 @@ impl Repo for SqliteRepo {
                 number,
                 play_count,
                 play_start_time,
-                play_duration
+                play_duration,
+                attributes,
+                position
             FROM
                 player
             WHERE
@@ -259,6 +436,8 @@ impl Repo for SqliteRepo {
                     play_count: row.get(3)?,
                     play_start_time: row.get(4)?,
                     play_duration: row.get(5)?,
+                    attributes_json: row.get(6)?,
+                    position: row.get(7)?,
                 };
 
                 Ok(Player::from(sql_row))
@@ -280,9 +459,11 @@ impl Repo for SqliteRepo {
                 number = ?2,
                 play_count = ?3,
 		play_start_time = ?4,
-		play_duration = ?5
+		play_duration = ?5,
+                attributes = ?6,
+                position = ?7
             WHERE
-                id = ?6
+                id = ?8
             ",
         )?;
 
@@ -295,6 +476,8 @@ impl Repo for SqliteRepo {
                 row.play_count,
                 row.play_start_time,
                 row.play_duration,
+                row.attributes_json,
+                row.position,
                 row.id,
             ))
             .map_err(Error::from)?;
@@ -331,14 +514,81 @@ impl Repo for SqliteRepo {
         }
     }
 
-    fn count_games(&self) -> Result<usize, Error> {
+    fn import_players(
+        &self,
+        created: Vec<Player>,
+        updated: Vec<Player>,
+    ) -> Result<Vec<Player>, Error> {
+        let mut conn = self.get_conn()?;
+        // dropping the transaction on error rolls it back.
+        let tx = conn.transaction()?;
+        let mut players = vec![];
+
+        {
+            let mut insert = tx.prepare(
+                "
+                INSERT INTO
+                    player
+                    (name, number, attributes, position)
+                VALUES
+                    (?1, ?2, ?3, ?4)
+                RETURNING
+                    id
+                ",
+            )?;
+            for player in created {
+                let row = PlayerSqlRow::from(player.clone());
+                let id: u32 = insert.query_one(
+                    (row.name, row.number, row.attributes_json, row.position),
+                    |r| r.get(0),
+                )?;
+                players.push(Player { id, ..player });
+            }
+
+            let mut update = tx.prepare(
+                "
+                UPDATE
+                    player
+                SET
+                    name = ?1,
+                    number = ?2,
+                    attributes = ?3,
+                    position = ?4
+                WHERE
+                    id = ?5
+                ",
+            )?;
+            for player in updated {
+                let row = PlayerSqlRow::from(player.clone());
+                let count = update.execute((
+                    row.name,
+                    row.number,
+                    row.attributes_json,
+                    row.position,
+                    row.id,
+                ))?;
+                if count != 1 {
+                    return Err(Error::NotFound);
+                }
+                players.push(player);
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(players)
+    }
+
+    fn next_game_id(&self) -> Result<u32, Error> {
         let conn = self.get_conn()?;
 
-        let count = conn
-            .query_row("SELECT count(id) from game", [], |row| row.get(0))
+        let id = conn
+            .query_row("SELECT COALESCE(MAX(id), 0) + 1 FROM game", [], |row| {
+                row.get(0)
+            })
             .map_err(Error::from)?;
 
-        Ok(count)
+        Ok(id)
     }
 
     fn list_games(&self) -> Result<Vec<Game>, Error> {
@@ -372,10 +622,20 @@ impl Repo for SqliteRepo {
             })
             .collect::<Result<Vec<Game>, _>>()?;
 
+        let mut votes = select_votes(&conn, None)?;
+        let games = games
+            .into_iter()
+            .map(|mut game| {
+                game.set_votes(votes.remove(&game.id).unwrap_or_default());
+                game
+            })
+            .collect();
+
         Ok(games)
     }
 
-    fn create_game(&self, game: Game) -> Result<(), Error> {
+    fn create_game(&self, mut game: Game) -> Result<(), Error> {
+        game.shared.touch();
         let conn = self.get_conn()?;
 
         let row = GameSqlRow::try_from(game)?;
@@ -421,10 +681,15 @@ impl Repo for SqliteRepo {
             })
             .map_err(Error::from)?;
 
-        Game::try_from(row)
+        let mut game = Game::try_from(row)?;
+        let mut votes = select_votes(&conn, Some(game_id))?;
+        game.set_votes(votes.remove(game_id).unwrap_or_default());
+
+        Ok(game)
     }
 
-    fn update_game(&self, game: Game) -> Result<(), Error> {
+    fn update_game(&self, mut game: Game) -> Result<(), Error> {
+        game.shared.touch();
         let conn = self.get_conn()?;
 
         let mut stmt = conn.prepare(
@@ -467,6 +732,7 @@ impl Repo for SqliteRepo {
         )?;
 
         let result = stmt.execute([game_id]).map_err(Error::from)?;
+        conn.execute("DELETE FROM vote WHERE game_id = ?1", [game_id])?;
 
         match result {
             0 => Err(Error::NotFound),
@@ -476,4 +742,209 @@ impl Repo for SqliteRepo {
             ))),
         }
     }
+
+    fn cast_vote(&self, game_id: &u32, vote: Vote) -> Result<(), Error> {
+        let conn = self.get_conn()?;
+
+        let exists: bool = conn.query_row(
+            "SELECT count(*) > 0 FROM game WHERE id = ?1",
+            [game_id],
+            |row| row.get(0),
+        )?;
+        if !exists {
+            return Err(Error::NotFound);
+        }
+
+        insert_votes(&conn, game_id, &[vote])
+    }
+
+    fn get_settings(&self) -> Result<Settings, Error> {
+        let conn = self.get_conn()?;
+
+        let data: Option<String> = conn
+            .query_row("SELECT data FROM settings WHERE id = 1", [], |row| {
+                row.get(0)
+            })
+            .map(Some)
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None), // never saved, use defaults.
+                _ => Err(Error::from(e)),
+            })?;
+
+        data.map_or_else(
+            || Ok(Settings::default()),
+            |d| serde_json::from_str(&d).map_err(|e| Error::Internal(e.to_string())),
+        )
+    }
+
+    fn update_settings(&self, settings: Settings) -> Result<(), Error> {
+        let conn = self.get_conn()?;
+
+        let data = serde_json::to_string(&settings).map_err(|e| Error::Internal(e.to_string()))?;
+
+        conn.execute(
+            "
+            INSERT INTO
+                settings
+                (id, data)
+            VALUES
+                (1, ?1)
+            ON CONFLICT(id) DO UPDATE SET
+                data = excluded.data
+            ",
+            [data],
+        )
+        .map_err(Error::from)?;
+
+        Ok(())
+    }
+
+    fn get_calendar_token(&self) -> Result<String, Error> {
+        let conn = self.get_conn()?;
+
+        conn.query_row("SELECT token FROM calendar WHERE id = 1", [], |row| {
+            row.get(0)
+        })
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(String::new()), // never enabled.
+            _ => Err(Error::from(e)),
+        })
+    }
+
+    fn update_calendar_token(&self, token: &str) -> Result<(), Error> {
+        let conn = self.get_conn()?;
+
+        _ = conn.execute(
+            "
+            INSERT INTO
+                calendar
+                (id, token)
+            VALUES
+                (1, ?1)
+            ON CONFLICT(id) DO UPDATE SET
+                token = excluded.token
+            ",
+            [token],
+        )?;
+
+        Ok(())
+    }
+
+    fn restore(
+        &self,
+        settings: Option<Settings>,
+        players: Vec<Player>,
+        games: Vec<Game>,
+        replace: bool,
+    ) -> Result<(), Error> {
+        let mut conn = self.get_conn()?;
+        // dropping the transaction on error rolls it back.
+        let tx = conn.transaction()?;
+
+        if replace {
+            tx.execute_batch("DELETE FROM player; DELETE FROM game; DELETE FROM vote;")?;
+        }
+
+        if let Some(settings) = settings {
+            let data =
+                serde_json::to_string(&settings).map_err(|e| Error::Internal(e.to_string()))?;
+            tx.execute(
+                "
+                INSERT INTO
+                    settings
+                    (id, data)
+                VALUES
+                    (1, ?1)
+                ON CONFLICT(id) DO UPDATE SET
+                    data = excluded.data
+                ",
+                [data],
+            )?;
+        }
+
+        {
+            let mut insert = tx.prepare(
+                "
+                INSERT INTO
+                    player
+                    (id, name, number, play_count, play_start_time, play_duration, attributes, position)
+                VALUES
+                    (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+                ",
+            )?;
+            for player in players {
+                let row = PlayerSqlRow::from(player);
+                insert
+                    .execute((
+                        row.id,
+                        row.name,
+                        row.number,
+                        row.play_count,
+                        row.play_start_time,
+                        row.play_duration,
+                        row.attributes_json,
+                        row.position,
+                    ))
+                    .map_err(conflict)?;
+            }
+
+            let mut insert = tx.prepare(
+                "
+                INSERT INTO
+                    game
+                    (id, shared, state)
+                VALUES
+                    (?1, ?2, ?3)
+                ",
+            )?;
+            for mut game in games {
+                let votes = game.take_votes();
+                insert_votes(&tx, &game.id, &votes)?;
+                let row = GameSqlRow::try_from(game)?;
+                insert
+                    .execute((row.id, row.shared_json, row.state_json))
+                    .map_err(conflict)?;
+            }
+        }
+
+        tx.commit()?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_migrate_seasons() {
+        let path = std::env::temp_dir().join(format!("subbers-seasons-{}.sql", std::process::id()));
+        {
+            let repo = SqliteRepo::new(Some(path.clone())).unwrap();
+            repo.update_settings(Settings::default()).unwrap();
+            let mut game = Game::new(1, vec![], Settings::default());
+            game.shared.scheduled_at = Some(Utc.with_ymd_and_hms(2023, 5, 6, 9, 0, 0).unwrap());
+            repo.create_game(game).unwrap();
+
+            // as saved before seasons were added.
+            let conn = repo.get_conn().unwrap();
+            conn.execute_batch(
+                "
+                UPDATE settings SET data = json_remove(data, '$.season');
+                UPDATE game SET shared = json_remove(shared, '$.settings.season');
+                ",
+            )
+            .unwrap();
+        }
+
+        let repo = SqliteRepo::new(Some(path.clone())).unwrap();
+        _ = std::fs::remove_file(&path);
+        assert_eq!(repo.get_game(&1).unwrap().shared.settings.season, "2023");
+        assert_eq!(
+            repo.get_settings().unwrap().season,
+            Utc::now().year().to_string()
+        );
+    }
 }
@@ -0,0 +1,214 @@
+use crate::player::{Attribute, Player};
+use chrono::{Datelike, Utc};
+use serde::{Deserialize, Serialize};
+
+/// `Settings` represents the team wide configuration. A copy is taken when each `Game` is created
+/// so changing settings does not alter past games.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)] // fields added later are missing from older saved settings.
+pub struct Settings {
+    pub lineup_rules: Vec<LineupRule>,
+    /// highlight players resting on the bench longer than this, 0 disables.
+    pub bench_warning_minutes: u32,
+    /// personal fouls before a player fouls out, 0 disables.
+    pub foul_out_limit: u32,
+    /// team fouls in a period before the opposition is in the bonus, 0 disables.
+    pub team_foul_bonus: u32,
+    /// players allowed on court at once, 0 is unlimited.
+    pub on_court_limit: u32,
+    /// sin bin suspension lengths, a red card is the rest of the game.
+    pub green_card_minutes: u32,
+    pub yellow_card_minutes: u32,
+    /// season label games are grouped by for season totals, eg: "2025" or "2025 Winter". New
+    /// settings start in the current year, settings saved before seasons were added load empty
+    /// rather than drifting into each new year, until the repo migrates them.
+    #[serde(default)]
+    pub season: String,
+    pub sub_rule: SubRule,
+    /// maximum substitutions or windows for `SubRule::MaxSubs` and `SubRule::MaxWindows`.
+    pub sub_limit: u32,
+    /// awards given each game alongside the MVP, eg: "best defender".
+    pub award_categories: Vec<String>,
+    /// minimum participation each player at a game is owed, 0 disables.
+    pub min_periods: u32,
+    pub min_minutes: u32,
+    /// court or pitch drawn for recording shots.
+    pub surface: Surface,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            lineup_rules: vec![],
+            bench_warning_minutes: 10,
+            foul_out_limit: 5,
+            team_foul_bonus: 5,
+            on_court_limit: 0,
+            green_card_minutes: 2,
+            yellow_card_minutes: 10,
+            season: Utc::now().year().to_string(),
+            sub_rule: SubRule::Rolling,
+            sub_limit: 0,
+            award_categories: vec![],
+            min_periods: 0,
+            min_minutes: 0,
+            surface: Surface::Basketball,
+        }
+    }
+}
+
+/// `SubRule` restricts when substitutions can be made during a period. Choosing the lineup at the
+/// start of each period is never restricted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubRule {
+    Rolling,
+    MaxSubs,
+    MaxWindows,
+    HalfTimeOnly,
+}
+
+impl SubRule {
+    pub const ALL: [SubRule; 4] = [
+        SubRule::Rolling,
+        SubRule::MaxSubs,
+        SubRule::MaxWindows,
+        SubRule::HalfTimeOnly,
+    ];
+}
+
+impl std::fmt::Display for SubRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Rolling => write!(f, "rolling"),
+            Self::MaxSubs => write!(f, "max-subs"),
+            Self::MaxWindows => write!(f, "max-windows"),
+            Self::HalfTimeOnly => write!(f, "half-time-only"),
+        }
+    }
+}
+
+/// `Surface` is the court or pitch shots are recorded on, drawn as the attacking end with the goal
+/// at the top. Shots are located in the surface's units, so each game keeps the surface it was
+/// played on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Surface {
+    Basketball,
+    Netball,
+    Football,
+    /// an outline only, for other sports.
+    Plain,
+}
+
+impl Surface {
+    pub const ALL: [Surface; 4] = [
+        Surface::Basketball,
+        Surface::Netball,
+        Surface::Football,
+        Surface::Plain,
+    ];
+
+    /// `width` is the diagram width in the units shots are located in, 0 to `width`.
+    pub fn width(self) -> u32 {
+        100
+    }
+
+    /// `length` is the diagram length in the units shots are located in, 0 being the goal end.
+    pub fn length(self) -> u32 {
+        match self {
+            Self::Basketball => 94, // half court, 15m by 14m.
+            Self::Netball => 67,    // goal third, 15.25m by 10.17m.
+            Self::Football => 77,   // half pitch, 68m by 52.5m.
+            Self::Plain => 100,
+        }
+    }
+}
+
+impl std::fmt::Display for Surface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Basketball => write!(f, "basketball"),
+            Self::Netball => write!(f, "netball"),
+            Self::Football => write!(f, "football"),
+            Self::Plain => write!(f, "plain"),
+        }
+    }
+}
+
+/// `Limit` is the direction a `LineupRule` restricts the on court lineup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Limit {
+    AtLeast,
+    AtMost,
+}
+
+impl std::fmt::Display for Limit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::AtLeast => write!(f, "at least"),
+            Self::AtMost => write!(f, "at most"),
+        }
+    }
+}
+
+/// `LineupRule` describes how many on court players may have an `Attribute`, for example
+/// "at least 1 ball-handler" or "at most 2 beginner".
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LineupRule {
+    pub attribute: Attribute,
+    pub limit: Limit,
+    pub count: u32,
+}
+
+impl std::fmt::Display for LineupRule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {} {}", self.limit, self.count, self.attribute)
+    }
+}
+
+impl LineupRule {
+    /// `check` returns a warning when the on court `players` break the rule.
+    pub fn check(&self, players: &[Player]) -> Option<String> {
+        let on_court = players
+            .iter()
+            .filter(|p| p.is_playing() && p.has_attribute(&self.attribute))
+            .count() as u32;
+
+        let broken = match self.limit {
+            Limit::AtLeast => on_court < self.count,
+            Limit::AtMost => on_court > self.count,
+        };
+
+        broken.then(|| format!("{self} on court, currently {on_court}"))
+    }
+}
+
+impl Settings {
+    /// `lineup_warnings` evaluates every `LineupRule` against the on court `players`. An empty
+    /// court is not warned about as the lineup is still being chosen.
+    pub fn lineup_warnings(&self, players: &[Player]) -> Vec<String> {
+        if !players.iter().any(Player::is_playing) {
+            return vec![];
+        }
+
+        self.lineup_rules
+            .iter()
+            .filter_map(|r| r.check(players))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_season_default() {
+        // saved before seasons were added.
+        let settings: Settings = serde_json::from_str("{}").unwrap();
+        assert_eq!(settings.season, "");
+        assert_eq!(Settings::default().season, Utc::now().year().to_string());
+    }
+}
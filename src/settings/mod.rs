@@ -0,0 +1,6 @@
+//! `settings` contains the team `Settings`, such as lineup rules, that apply to each new game.
+
+mod core;
+
+// re-export some objects to reduce use import stuttering.
+pub use core::{Limit, LineupRule, Settings, SubRule, Surface};
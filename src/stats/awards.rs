@@ -0,0 +1,78 @@
+use super::{PlayerTotal, in_season, oldest_first, player_total};
+use crate::Game;
+
+/// `MVP` labels the MVP alongside the team's award categories.
+pub const MVP: &str = "MVP";
+
+/// `AwardTally` counts how many times a player has received each award in a season, `counts`
+/// matching the order of the categories returned by `award_tallies`.
+#[derive(Clone, Debug, Default)]
+pub struct AwardTally {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    pub counts: Vec<u32>,
+}
+
+impl PlayerTotal for AwardTally {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+impl AwardTally {
+    pub fn total(&self) -> u32 {
+        self.counts.iter().sum()
+    }
+}
+
+/// `award_tallies` counts the MVPs and other awards per player for the `season`, returning the
+/// categories given out, MVP first, and a tally for each player with at least one award.
+pub fn award_tallies(games: &[Game], season: &str) -> (Vec<String>, Vec<AwardTally>) {
+    let mut categories = vec![MVP.to_string()];
+    let mut awards: Vec<(String, u32, &Game)> = vec![];
+
+    for game in oldest_first(in_season(games, season)) {
+        if !game.state.kind().is_played() {
+            continue;
+        }
+
+        if let Some(player_id) = game.shared.mvp {
+            awards.push((MVP.to_string(), player_id, game));
+        }
+        for award in &game.shared.awards {
+            if !categories.contains(&award.category) {
+                categories.push(award.category.clone());
+            }
+            awards.push((award.category.clone(), award.player_id, game));
+        }
+    }
+
+    let mut tallies: Vec<AwardTally> = vec![];
+    for (category, player_id, game) in awards {
+        let player = game.shared.players.iter().find(|p| p.id == player_id);
+        let tally = player_total(
+            &mut tallies,
+            player_id,
+            player.map_or(0, |p| p.number),
+            &player.map_or_else(|| player_id.to_string(), |p| p.name.clone()),
+            || AwardTally {
+                counts: vec![0; categories.len()],
+                ..AwardTally::default()
+            },
+        );
+
+        if let Some(c) = categories.iter().position(|c| c == &category) {
+            tally.counts[c] += 1;
+        }
+    }
+
+    tallies.sort_by_key(|t| t.name.to_lowercase());
+    (categories, tallies)
+}
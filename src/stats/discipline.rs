@@ -0,0 +1,73 @@
+use super::{PlayerTotal, in_season, oldest_first, player_total};
+use crate::Game;
+use crate::game::CardKind;
+
+/// `DisciplineTotals` sums a player's fouls and cards across a season.
+#[derive(Clone, Debug, Default)]
+pub struct DisciplineTotals {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    pub fouls: u32,
+    pub green: u32,
+    pub yellow: u32,
+    pub red: u32,
+}
+
+impl PlayerTotal for DisciplineTotals {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+/// `discipline_totals` sums fouls and cards per player for the `season`, ignoring games not yet
+/// started. Players without any fouls or cards are left out.
+pub fn discipline_totals(games: &[Game], season: &str) -> Vec<DisciplineTotals> {
+    let mut totals: Vec<DisciplineTotals> = vec![];
+
+    for game in oldest_first(in_season(games, season)) {
+        if !game.state.kind().is_played() {
+            continue;
+        }
+
+        for player in &game.shared.players {
+            let fouls = game.player_fouls(&player.id);
+            let cards: Vec<CardKind> = game
+                .shared
+                .cards
+                .iter()
+                .filter(|c| c.player_id == player.id)
+                .map(|c| c.kind)
+                .collect();
+
+            if fouls == 0 && cards.is_empty() {
+                continue;
+            }
+
+            let total = player_total(
+                &mut totals,
+                player.id,
+                player.number,
+                &player.name,
+                DisciplineTotals::default,
+            );
+            total.fouls += fouls;
+            for card in cards {
+                match card {
+                    CardKind::Green => total.green += 1,
+                    CardKind::Yellow => total.yellow += 1,
+                    CardKind::Red => total.red += 1,
+                }
+            }
+        }
+    }
+
+    totals.sort_by_key(|t| t.name.to_lowercase());
+    totals
+}
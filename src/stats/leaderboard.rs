@@ -0,0 +1,250 @@
+use super::{PlayerTotal, oldest_first, player_total};
+use crate::player::duration;
+use crate::{Game, GameView};
+use chrono::{NaiveDate, TimeDelta};
+use serde::Deserialize;
+
+/// `LeaderboardFilter` narrows the games a leaderboard is calculated from.
+#[derive(Clone, Debug, Default)]
+pub struct LeaderboardFilter {
+    pub season: String,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    /// matched ignoring case, `None` for all opponents.
+    pub opponent: Option<String>,
+}
+
+impl LeaderboardFilter {
+    pub(super) fn matches(&self, game: &Game) -> bool {
+        let date = game.state.start_time().map(|st| st.date_naive());
+
+        game.shared.settings.season == self.season
+            && game.state.kind().is_played()
+            && self.from.is_none_or(|from| date.is_some_and(|d| d >= from))
+            && self.to.is_none_or(|to| date.is_some_and(|d| d <= to))
+            && self
+                .opponent
+                .as_ref()
+                .is_none_or(|o| game.shared.opponent.eq_ignore_ascii_case(o))
+    }
+}
+
+/// `LeaderboardSort` is the column a leaderboard is sorted by, highest first except `Name`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LeaderboardSort {
+    Name,
+    Games,
+    #[default]
+    Minutes,
+    AverageMinutes,
+    Available,
+    Mvps,
+    Awards,
+    Points,
+    Streak,
+}
+
+impl std::fmt::Display for LeaderboardSort {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Name => write!(f, "name"),
+            Self::Games => write!(f, "games"),
+            Self::Minutes => write!(f, "minutes"),
+            Self::AverageMinutes => write!(f, "average-minutes"),
+            Self::Available => write!(f, "available"),
+            Self::Mvps => write!(f, "mvps"),
+            Self::Awards => write!(f, "awards"),
+            Self::Points => write!(f, "points"),
+            Self::Streak => write!(f, "streak"),
+        }
+    }
+}
+
+/// `LeaderboardRow` is a player's totals across the filtered games.
+#[derive(Clone, Debug, Default)]
+pub struct LeaderboardRow {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    /// games the player got on court in.
+    pub games: u32,
+    pub played: TimeDelta,
+    pub available: TimeDelta,
+    pub mvps: u32,
+    /// awards other than MVP.
+    pub awards: u32,
+    pub points: u32,
+    /// games in a row attended, up to the latest game.
+    pub current_streak: u32,
+    pub longest_streak: u32,
+}
+
+impl PlayerTotal for LeaderboardRow {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+impl LeaderboardRow {
+    fn average_played_delta(&self) -> TimeDelta {
+        self.played / (self.games.max(1) as i32)
+    }
+
+    // the share of the time the player was at games that they played.
+    fn available_ratio(&self) -> f64 {
+        let available = self.available.num_milliseconds();
+        if available > 0 {
+            self.played.num_milliseconds() as f64 / available as f64
+        } else {
+            0.0
+        }
+    }
+
+    pub fn played(&self) -> String {
+        duration(self.played)
+    }
+
+    pub fn average_played(&self) -> String {
+        duration(self.average_played_delta())
+    }
+
+    pub fn available_percentage(&self) -> String {
+        format!("{:.0}%", self.available_ratio() * 100.0)
+    }
+}
+
+/// `leaderboard` totals each player's games, minutes, awards, points and attendance across the
+/// games matching `filter`, sorted by `sort`.
+pub fn leaderboard(
+    games: &[Game],
+    filter: &LeaderboardFilter,
+    sort: LeaderboardSort,
+) -> Vec<LeaderboardRow> {
+    // oldest first for attendance streaks too.
+    let games = oldest_first(games.iter().filter(|g| filter.matches(g)));
+
+    let mut rows: Vec<LeaderboardRow> = vec![];
+    for game in &games {
+        let view = GameView::from(*game);
+        for player in &view.players {
+            let row = player_total(
+                &mut rows,
+                player.id,
+                player.number,
+                &player.name,
+                LeaderboardRow::default,
+            );
+            if player.play_count > 0 {
+                row.games += 1;
+            }
+            row.played += player.played_duration();
+            row.available += player.available_duration;
+            row.points += player.points;
+            if view.mvp == Some(player.id) {
+                row.mvps += 1;
+            }
+            row.awards += view
+                .awards
+                .iter()
+                .filter(|a| a.player_id == player.id)
+                .count() as u32;
+        }
+    }
+
+    // a game the player isn't in the roster of, eg: they joined later, breaks the streak too.
+    for row in &mut rows {
+        for game in &games {
+            let attended = game
+                .shared
+                .players
+                .iter()
+                .find(|p| p.id == row.player_id)
+                .is_some_and(|p| p.availability().is_some());
+            if attended {
+                row.current_streak += 1;
+                row.longest_streak = row.longest_streak.max(row.current_streak);
+            } else {
+                row.current_streak = 0;
+            }
+        }
+    }
+
+    match sort {
+        LeaderboardSort::Name => rows.sort_by_key(|r| r.name.to_lowercase()),
+        LeaderboardSort::Games => rows.sort_by_key(|r| std::cmp::Reverse(r.games)),
+        LeaderboardSort::Minutes => rows.sort_by_key(|r| std::cmp::Reverse(r.played)),
+        LeaderboardSort::AverageMinutes => {
+            rows.sort_by_key(|r| std::cmp::Reverse(r.average_played_delta()))
+        }
+        LeaderboardSort::Available => {
+            rows.sort_by(|a, b| b.available_ratio().total_cmp(&a.available_ratio()))
+        }
+        LeaderboardSort::Mvps => rows.sort_by_key(|r| std::cmp::Reverse(r.mvps)),
+        LeaderboardSort::Awards => rows.sort_by_key(|r| std::cmp::Reverse(r.awards)),
+        LeaderboardSort::Points => rows.sort_by_key(|r| std::cmp::Reverse(r.points)),
+        LeaderboardSort::Streak => rows.sort_by_key(|r| std::cmp::Reverse(r.longest_streak)),
+    }
+
+    rows
+}
+
+/// `opponents` returns the distinct opponents of the `season`'s games, sorted.
+pub fn opponents(games: &[Game], season: &str) -> Vec<String> {
+    let mut opponents: Vec<String> = vec![];
+    for game in super::in_season(games, season) {
+        let opponent = &game.shared.opponent;
+        if !opponent.is_empty() && !opponents.iter().any(|o| o.eq_ignore_ascii_case(opponent)) {
+            opponents.push(opponent.clone());
+        }
+    }
+
+    opponents.sort_by_key(|o| o.to_lowercase());
+    opponents
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::{Player, Settings};
+
+    fn played(id: u32, players: Vec<Player>) -> Game {
+        Game::new(id, players, Settings::default())
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"))
+    }
+
+    #[test]
+    fn test_streak_resets_when_not_in_roster() {
+        let a = Player::new(1, 1, "a".to_string());
+        let b = Player::new(2, 2, "b".to_string());
+        let mut late = b.clone();
+        late.mark_late();
+        let games = vec![
+            played(1, vec![a.clone(), b.clone()]),
+            played(2, vec![a.clone(), b.clone()]),
+            played(3, vec![a.clone()]), // b skipped this game.
+            played(4, vec![a.clone(), b.clone()]),
+            played(5, vec![a.clone(), late]),
+            played(6, vec![a.clone(), b.clone()]),
+        ];
+        let filter = LeaderboardFilter {
+            season: Settings::default().season,
+            ..LeaderboardFilter::default()
+        };
+
+        let rows = leaderboard(&games, &filter, LeaderboardSort::Name);
+        let streaks: Vec<(u32, u32)> = rows
+            .iter()
+            .map(|r| (r.current_streak, r.longest_streak))
+            .collect();
+        assert_eq!(streaks, vec![(6, 6), (1, 2)]);
+    }
+}
@@ -0,0 +1,83 @@
+use super::{LeaderboardFilter, PlayerTotal, oldest_first, player_total};
+use crate::{Game, GameView};
+use chrono::TimeDelta;
+
+/// `PlayerMinutes` is a player's minutes and play count for each of the `SeasonMinutes` games,
+/// `None` when they weren't in the game.
+#[derive(Clone, Debug, Default)]
+pub struct PlayerMinutes {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    pub played: Vec<Option<TimeDelta>>,
+    pub play_counts: Vec<Option<u32>>,
+}
+
+impl PlayerTotal for PlayerMinutes {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+/// `SeasonMinutes` is a player by game grid of minutes played, oldest game first.
+#[derive(Clone, Debug, Default)]
+pub struct SeasonMinutes {
+    pub game_ids: Vec<u32>,
+    pub players: Vec<PlayerMinutes>,
+}
+
+impl SeasonMinutes {
+    pub fn max_played(&self) -> TimeDelta {
+        self.players
+            .iter()
+            .flat_map(|p| p.played.iter().flatten())
+            .max()
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn max_play_count(&self) -> u32 {
+        self.players
+            .iter()
+            .flat_map(|p| p.play_counts.iter().flatten())
+            .max()
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// `season_minutes` collects each player's minutes and play count per game matching `filter`.
+pub fn season_minutes(games: &[Game], filter: &LeaderboardFilter) -> SeasonMinutes {
+    let games = oldest_first(games.iter().filter(|g| filter.matches(g)));
+
+    let mut minutes = SeasonMinutes {
+        game_ids: games.iter().map(|g| g.id).collect(),
+        players: vec![],
+    };
+    for (index, game) in games.iter().enumerate() {
+        for player in GameView::from(*game).players {
+            let row = player_total(
+                &mut minutes.players,
+                player.id,
+                player.number,
+                &player.name,
+                || PlayerMinutes {
+                    played: vec![None; games.len()],
+                    play_counts: vec![None; games.len()],
+                    ..PlayerMinutes::default()
+                },
+            );
+            row.played[index] = Some(player.played_duration());
+            row.play_counts[index] = Some(player.play_count);
+        }
+    }
+
+    minutes.players.sort_by_key(|p| p.number);
+    minutes
+}
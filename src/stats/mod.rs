@@ -0,0 +1,141 @@
+//! `stats` contains season totals and other statistics calculated from the recorded games.
+
+mod awards;
+mod discipline;
+mod leaderboard;
+mod minutes;
+mod participation;
+mod player;
+mod plus_minus;
+mod shots;
+
+// re-export some objects to reduce use import stuttering.
+pub use awards::{AwardTally, MVP, award_tallies};
+pub use discipline::{DisciplineTotals, discipline_totals};
+pub use leaderboard::{LeaderboardFilter, LeaderboardRow, LeaderboardSort, leaderboard, opponents};
+pub use minutes::{PlayerMinutes, SeasonMinutes, season_minutes};
+pub use participation::{SeasonParticipation, season_finished_games, season_participation};
+pub use player::{PlayerGame, PlayerSeason, player_season};
+pub use plus_minus::{PlusMinusTotals, plus_minus_totals, season_lineups};
+pub use shots::{ShootingTotals, shooting_totals};
+
+use crate::Game;
+use crate::game::{IncidentEntry, NoteEntry};
+
+/// `seasons` returns the distinct season labels of the `games`, newest first.
+pub fn seasons(games: &[Game]) -> Vec<String> {
+    let mut seasons: Vec<String> = vec![];
+    for game in games {
+        if !seasons.contains(&game.shared.settings.season) {
+            seasons.push(game.shared.settings.season.clone());
+        }
+    }
+
+    seasons.sort_by(|a, b| b.cmp(a));
+    seasons
+}
+
+/// `incidents` lists the incidents in the `season`'s games, newest first.
+pub fn incidents(games: &[Game], season: &str) -> Vec<IncidentEntry> {
+    let mut incidents: Vec<IncidentEntry> = in_season(games, season)
+        .flat_map(Game::incident_entries)
+        .collect();
+
+    incidents.sort_by_key(|i| std::cmp::Reverse(i.time));
+    incidents
+}
+
+/// `player_notes` lists the notes about the player in the `season`'s games, optionally only those
+/// tagged `tag`, newest first.
+pub fn player_notes(
+    games: &[Game],
+    season: &str,
+    player_id: &u32,
+    tag: Option<&str>,
+) -> Vec<NoteEntry> {
+    let mut notes: Vec<NoteEntry> = in_season(games, season)
+        .flat_map(Game::note_entries)
+        .filter(|n| n.player_id.as_ref() == Some(player_id))
+        .filter(|n| tag.is_none_or(|t| n.tags.iter().any(|nt| nt == t)))
+        .collect();
+
+    notes.sort_by_key(|n| std::cmp::Reverse(n.time));
+    notes
+}
+
+/// `in_season` filters `games` down to those played in `season`.
+pub fn in_season<'a>(games: &'a [Game], season: &'a str) -> impl Iterator<Item = &'a Game> {
+    games
+        .iter()
+        .filter(move |g| g.shared.settings.season == season)
+}
+
+// oldest_first orders `games` by id, the order `player_total` needs them visited in.
+fn oldest_first<'a>(games: impl IntoIterator<Item = &'a Game>) -> Vec<&'a Game> {
+    let mut games: Vec<&Game> = games.into_iter().collect();
+    games.sort_by_key(|g| g.id);
+    games
+}
+
+/// `PlayerTotal` is a row of one player's totals across games, labelled with their number and
+/// name.
+trait PlayerTotal {
+    fn player_id(&self) -> u32;
+    fn label(&mut self, player_id: u32, number: u32, name: &str);
+}
+
+// player_total returns the player's row in `totals`, adding `new()` when they have none yet. The
+// row is labelled with the `number` and `name` from every game, so with games visited oldest first
+// a player who changed number or name is shown as they were in their latest game.
+fn player_total<'a, T: PlayerTotal>(
+    totals: &'a mut Vec<T>,
+    player_id: u32,
+    number: u32,
+    name: &str,
+    new: impl FnOnce() -> T,
+) -> &'a mut T {
+    let index = match totals.iter().position(|t| t.player_id() == player_id) {
+        Some(i) => i,
+        None => {
+            totals.push(new());
+            totals.len() - 1
+        }
+    };
+
+    let total = &mut totals[index];
+    total.label(player_id, number, name);
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Event;
+    use crate::{Player, Settings};
+
+    // fouled returns a started game where the player, numbered and named as given, fouled.
+    fn fouled(id: u32, number: u32, name: &str) -> Game {
+        let players = vec![Player::new(1, number, name.to_string())];
+        let mut game = Game::new(id, players, Settings::default())
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"));
+        assert!(game.record_foul(&1));
+        game
+    }
+
+    #[test]
+    fn test_latest_number_and_name_win() {
+        // listed newest first, as the repos return them.
+        let games = vec![fouled(2, 9, "Jo Lee"), fouled(1, 7, "Jo")];
+        let season = games[0].shared.settings.season.clone();
+
+        let totals = discipline_totals(&games, &season);
+        assert_eq!(totals.len(), 1);
+        assert_eq!((totals[0].number, totals[0].name.as_str()), (9, "Jo Lee"));
+        assert_eq!(totals[0].fouls, 2);
+
+        let games: Vec<Game> = games.into_iter().rev().collect();
+        let totals = discipline_totals(&games, &season);
+        assert_eq!((totals[0].number, totals[0].name.as_str()), (9, "Jo Lee"));
+    }
+}
@@ -0,0 +1,114 @@
+use super::{PlayerTotal, in_season, player_total};
+use crate::Game;
+use chrono::TimeDelta;
+
+/// `SeasonParticipation` is a player's minutes, awards and minimum participation record across the
+/// season's finished and abandoned games.
+#[derive(Clone, Debug, Default)]
+pub struct SeasonParticipation {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    /// games attended.
+    pub games: u32,
+    pub played: TimeDelta,
+    /// MVPs and other awards.
+    pub awards: u32,
+    pub compliant: u32,
+    /// game ids where the player was short of the minimum participation.
+    pub non_compliant: Vec<u32>,
+}
+
+impl PlayerTotal for SeasonParticipation {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+/// `season_finished_games` returns the `season`'s games that have ended, finished or abandoned,
+/// oldest first.
+pub fn season_finished_games(games: &[Game], season: &str) -> Vec<Game> {
+    let mut games: Vec<Game> = in_season(games, season)
+        .filter(|g| g.state.kind().has_ended())
+        .cloned()
+        .collect();
+
+    games.sort_by_key(|g| g.id);
+    games
+}
+
+/// `season_participation` totals each player's participation in the `season`'s ended games.
+pub fn season_participation(games: &[Game], season: &str) -> Vec<SeasonParticipation> {
+    let mut totals: Vec<SeasonParticipation> = vec![];
+    for game in season_finished_games(games, season) {
+        for participation in game.participation() {
+            let total = player_total(
+                &mut totals,
+                participation.player_id,
+                participation.number,
+                &participation.name,
+                SeasonParticipation::default,
+            );
+            if participation.attended {
+                total.games += 1;
+            }
+            total.played += participation.played;
+            if game.shared.mvp == Some(participation.player_id) {
+                total.awards += 1;
+            }
+            total.awards += game
+                .shared
+                .awards
+                .iter()
+                .filter(|a| a.player_id == participation.player_id)
+                .count() as u32;
+            match participation.compliant {
+                Some(true) => total.compliant += 1,
+                Some(false) => total.non_compliant.push(game.id),
+                None => {}
+            }
+        }
+    }
+
+    totals.sort_by_key(|t| t.number);
+    totals
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Settings;
+    use crate::game::Event;
+
+    fn game(id: u32, events: Vec<Event>) -> Game {
+        events
+            .into_iter()
+            .try_fold(Game::new(id, vec![], Settings::default()), |g, e| {
+                g.on_event(e)
+            })
+            .unwrap_or_else(|_| panic!("events should apply"))
+    }
+
+    #[test]
+    fn test_season_finished_games_includes_abandoned() {
+        let games = vec![
+            game(4, vec![Event::StartGame, Event::Abandon]),
+            game(3, vec![Event::StartGame]),
+            game(2, vec![]),
+            game(1, vec![Event::StartGame, Event::EndGame]),
+        ];
+
+        let season = Settings::default().season;
+        let ids: Vec<u32> = season_finished_games(&games, &season)
+            .iter()
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(ids, vec![1, 4]);
+    }
+}
@@ -0,0 +1,163 @@
+use crate::game::CardKind;
+use crate::player::Stint;
+use crate::player::duration;
+use crate::{Game, GameState, GameView};
+use chrono::{DateTime, TimeDelta, Utc};
+
+// TREND_GAMES is how many recent games are compared against the rest of the season.
+const TREND_GAMES: usize = 3;
+
+/// `PlayerGame` is a player's record of a game they were in.
+#[derive(Clone, Debug)]
+pub struct PlayerGame {
+    pub game_id: u32,
+    pub start_time: Option<DateTime<Utc>>,
+    pub state: GameState,
+    pub played: TimeDelta,
+    pub play_count: u32,
+    /// stints on court, the current stint ending now.
+    pub stints: Vec<Stint>,
+    pub available_percentage: String,
+    pub awards: Vec<String>,
+    pub points: u32,
+    pub fouls: u32,
+    pub cards: Vec<CardKind>,
+    pub plus_minus: i64,
+    pub notes: Vec<String>,
+}
+
+impl PlayerGame {
+    pub fn played(&self) -> String {
+        duration(self.played)
+    }
+}
+
+/// `PlayerSeason` totals a player's games in a season, see `player_season`.
+#[derive(Clone, Debug, Default)]
+pub struct PlayerSeason {
+    pub games: Vec<PlayerGame>, // oldest first.
+    pub played: TimeDelta,
+    pub play_count: u32,
+    pub points: u32,
+    pub fouls: u32,
+    pub awards: u32,
+    pub plus_minus: i64,
+}
+
+impl PlayerSeason {
+    fn per_game(&self, total: f64) -> String {
+        format!("{:.1}", total / self.games.len().max(1) as f64)
+    }
+
+    pub fn played(&self) -> String {
+        duration(self.played)
+    }
+
+    pub fn average_played(&self) -> String {
+        duration(self.played / (self.games.len().max(1) as i32))
+    }
+
+    pub fn average_play_count(&self) -> String {
+        self.per_game(self.play_count.into())
+    }
+
+    pub fn average_points(&self) -> String {
+        self.per_game(self.points.into())
+    }
+
+    pub fn average_plus_minus(&self) -> String {
+        format!(
+            "{:+.1}",
+            self.plus_minus as f64 / self.games.len().max(1) as f64
+        )
+    }
+
+    /// `minutes_trend` compares the average time played in the last few games with the games
+    /// before them, `None` until there are enough games to compare.
+    pub fn minutes_trend(&self) -> Option<String> {
+        if self.games.len() <= TREND_GAMES {
+            return None;
+        }
+
+        let (earlier, recent) = self.games.split_at(self.games.len() - TREND_GAMES);
+        let average = |games: &[PlayerGame]| {
+            games.iter().map(|g| g.played.num_seconds()).sum::<i64>() / games.len() as i64
+        };
+        let change = average(recent) - average(earlier);
+
+        Some(format!(
+            "{} {} per game over the last {TREND_GAMES} games",
+            if change >= 0 { "up" } else { "down" },
+            duration(TimeDelta::seconds(change.abs()))
+        ))
+    }
+}
+
+/// `player_season` collects the player's record of each `season` game they were in, games not
+/// yet started being left out.
+pub fn player_season(games: &[Game], season: &str, player_id: &u32) -> PlayerSeason {
+    let mut season_games: Vec<&Game> = super::in_season(games, season)
+        .filter(|g| g.state.kind().is_played())
+        .filter(|g| g.shared.players.iter().any(|p| &p.id == player_id))
+        .collect();
+    season_games.sort_by_key(|g| g.id);
+
+    let mut totals = PlayerSeason::default();
+    for game in season_games {
+        let view = GameView::from(game);
+        let Some(player) = view.players.iter().find(|p| &p.id == player_id) else {
+            continue;
+        };
+        let game_player = game.shared.players.iter().find(|p| &p.id == player_id);
+
+        let mut stints = game_player.map(|p| p.stints.clone()).unwrap_or_default();
+        if let Some(start_time) = player.play_start_time {
+            stints.push(Stint {
+                start_time,
+                end_time: Utc::now(),
+            });
+        }
+
+        let mut awards = vec![];
+        if view.mvp == Some(*player_id) {
+            awards.push(super::MVP.to_string());
+        }
+        awards.extend(
+            view.awards
+                .iter()
+                .filter(|a| &a.player_id == player_id)
+                .map(|a| a.category.clone()),
+        );
+
+        let record = PlayerGame {
+            game_id: view.id,
+            start_time: view.start_time,
+            state: view.state,
+            played: player.played_duration(),
+            play_count: player.play_count,
+            stints,
+            available_percentage: player.available_percentage(),
+            awards,
+            points: player.points,
+            fouls: player.fouls,
+            cards: player.cards.clone(),
+            plus_minus: player.plus_minus,
+            notes: view
+                .notes
+                .iter()
+                .filter(|n| n.player_id.as_ref() == Some(player_id))
+                .map(|n| n.text.clone())
+                .collect(),
+        };
+
+        totals.played += record.played;
+        totals.play_count += record.play_count;
+        totals.points += record.points;
+        totals.fouls += record.fouls;
+        totals.awards += record.awards.len() as u32;
+        totals.plus_minus += record.plus_minus;
+        totals.games.push(record);
+    }
+
+    totals
+}
@@ -0,0 +1,145 @@
+use super::{PlayerTotal, in_season, oldest_first, player_total};
+use crate::Game;
+use crate::game::Lineup;
+use std::collections::HashMap;
+
+/// `PlusMinusTotals` sums a player's points and plus/minus across the season's games they played.
+#[derive(Clone, Debug, Default)]
+pub struct PlusMinusTotals {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    pub games: u32,
+    pub points: u32,
+    pub plus_minus: i64,
+}
+
+impl PlayerTotal for PlusMinusTotals {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+impl PlusMinusTotals {
+    pub fn average_points(&self) -> String {
+        format!("{:.1}", self.points as f64 / self.games.max(1) as f64)
+    }
+
+    pub fn average_plus_minus(&self) -> String {
+        format!("{:+.1}", self.plus_minus as f64 / self.games.max(1) as f64)
+    }
+}
+
+/// `plus_minus_totals` sums points and plus/minus per player for the `season`, only counting games
+/// the player got on court in.
+pub fn plus_minus_totals(games: &[Game], season: &str) -> Vec<PlusMinusTotals> {
+    let mut totals: Vec<PlusMinusTotals> = vec![];
+
+    for game in oldest_first(in_season(games, season)) {
+        if !game.state.kind().is_played() {
+            continue;
+        }
+
+        for player in game.shared.players.iter().filter(|p| p.play_count > 0) {
+            let total = player_total(
+                &mut totals,
+                player.id,
+                player.number,
+                &player.name,
+                PlusMinusTotals::default,
+            );
+            total.games += 1;
+            total.points += game.player_points(&player.id);
+            total.plus_minus += game.plus_minus(&player.id);
+        }
+    }
+
+    totals.sort_by_key(|t| std::cmp::Reverse(t.plus_minus));
+    totals
+}
+
+/// `season_lineups` combines each game's `Lineup`s for the `season`, best plus/minus first.
+pub fn season_lineups(games: &[Game], season: &str) -> Vec<Lineup> {
+    let mut lineups: HashMap<Vec<u32>, Lineup> = HashMap::new();
+
+    for game in in_season(games, season) {
+        for lineup in game.lineups() {
+            match lineups.get_mut(&lineup.player_ids) {
+                Some(l) => {
+                    l.points_for += lineup.points_for;
+                    l.points_against += lineup.points_against;
+                }
+                None => _ = lineups.insert(lineup.player_ids.clone(), lineup),
+            }
+        }
+    }
+
+    let mut lineups: Vec<Lineup> = lineups.into_values().collect();
+    lineups.sort_by_key(|l| (std::cmp::Reverse(l.plus_minus()), l.player_ids.clone()));
+    lineups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{Event, Team};
+    use crate::{Player, Settings};
+
+    // ended returns a finished game where player 1 scored `points` on court and, when
+    // `two_played`, player 2 was on court too.
+    fn ended(id: u32, points: u32, two_played: bool) -> Game {
+        let players = (1..=2)
+            .map(|id| Player::new(id, id, id.to_string()))
+            .collect();
+        let mut game = Game::new(id, players, Settings::default())
+            .on_event(Event::StartGame)
+            .unwrap_or_else(|_| panic!("game should start"));
+        game.shared.players[0].sub_on();
+        if two_played {
+            game.shared.players[1].sub_on();
+        }
+        assert!(game.record_score(Team::Us, Some(1), points));
+        assert!(game.record_score(Team::Them, None, 1));
+        for player in &mut game.shared.players {
+            player.sub_off();
+        }
+
+        game.on_event(Event::EndGame)
+            .unwrap_or_else(|_| panic!("game should end"))
+    }
+
+    #[test]
+    fn test_plus_minus_totals() {
+        let games = vec![ended(2, 3, false), ended(1, 2, true)];
+        let season = games[0].shared.settings.season.clone();
+        let totals = plus_minus_totals(&games, &season);
+
+        assert_eq!(totals.len(), 2);
+        assert_eq!(totals[0].player_id, 1);
+        assert_eq!((totals[0].games, totals[0].points), (2, 5));
+        assert_eq!(totals[0].plus_minus, 3);
+        assert_eq!(totals[0].average_plus_minus(), "+1.5");
+        assert_eq!((totals[1].games, totals[1].plus_minus), (1, 1));
+
+        assert!(plus_minus_totals(&games, "1999").is_empty());
+    }
+
+    #[test]
+    fn test_season_lineups() {
+        let games = vec![ended(2, 3, false), ended(1, 2, true), ended(3, 2, true)];
+        let season = games[0].shared.settings.season.clone();
+        let lineups = season_lineups(&games, &season);
+
+        assert_eq!(lineups.len(), 2);
+        assert_eq!(lineups[0].player_ids, vec![1]);
+        assert_eq!((lineups[0].points_for, lineups[0].points_against), (3, 1));
+        assert_eq!(lineups[1].player_ids, vec![1, 2]);
+        assert_eq!((lineups[1].points_for, lineups[1].points_against), (4, 2));
+    }
+}
@@ -0,0 +1,61 @@
+use super::{PlayerTotal, oldest_first, player_total};
+use crate::Game;
+
+/// `ShootingTotals` counts a player's shots made and attempted.
+#[derive(Clone, Debug, Default)]
+pub struct ShootingTotals {
+    pub player_id: u32,
+    pub number: u32,
+    pub name: String,
+    pub made: u32,
+    pub attempts: u32,
+}
+
+impl PlayerTotal for ShootingTotals {
+    fn player_id(&self) -> u32 {
+        self.player_id
+    }
+
+    fn label(&mut self, player_id: u32, number: u32, name: &str) {
+        self.player_id = player_id;
+        self.number = number;
+        self.name = name.to_string();
+    }
+}
+
+impl ShootingTotals {
+    pub fn ratio(&self) -> f64 {
+        self.made as f64 / self.attempts.max(1) as f64
+    }
+
+    pub fn percentage(&self) -> String {
+        format!("{:.0}%", self.ratio() * 100.0)
+    }
+}
+
+/// `shooting_totals` counts shots made and attempted per player across the `games`, players
+/// without any shots are left out.
+pub fn shooting_totals<'a>(games: impl IntoIterator<Item = &'a Game>) -> Vec<ShootingTotals> {
+    let mut totals: Vec<ShootingTotals> = vec![];
+
+    for game in oldest_first(games) {
+        for shot in &game.shared.shots {
+            let player = game.shared.players.iter().find(|p| p.id == shot.player_id);
+            let total = player_total(
+                &mut totals,
+                shot.player_id,
+                player.map_or(0, |p| p.number),
+                &player.map_or_else(|| shot.player_id.to_string(), |p| p.name.clone()),
+                ShootingTotals::default,
+            );
+
+            total.attempts += 1;
+            if shot.made {
+                total.made += 1;
+            }
+        }
+    }
+
+    totals.sort_by_key(|t| t.name.to_lowercase());
+    totals
+}
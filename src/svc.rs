@@ -1,12 +1,23 @@
 //! `Svc` contains the main `Service` struct, which can be interacted with to manage sports games.
 
+use chrono::Utc;
 use std::sync::Arc;
 
 // TODO: may move/change re-export.
 use super::Error;
 use super::Player;
 use super::Repo;
-use super::{EventError, Game};
+use super::Settings;
+use super::dump::{DUMP_VERSION, Dump, ImportMode, ImportSummary};
+use super::export;
+use super::game::{CardKind, Fixture, IncidentEntry, Lineup, NoteEntry, Shot, Team};
+use super::import::{self, RosterImport, RosterStatus};
+use super::settings::{LineupRule, Surface};
+use super::stats::{
+    self, AwardTally, DisciplineTotals, LeaderboardFilter, LeaderboardRow, LeaderboardSort,
+    PlayerSeason, PlusMinusTotals, SeasonMinutes, SeasonParticipation, ShootingTotals,
+};
+use super::{EventError, Game, GameState};
 
 /// `Service` provides `Game` and `Player` management services, storing data in its repository.
 #[derive(Clone)]
@@ -41,6 +52,45 @@ impl Service {
         self.repo.update_player(player.clone()).map(|()| player)
     }
 
+    pub fn preview_roster(&self, csv: &str) -> Result<Vec<RosterImport>, Error> {
+        let rows = import::parse_roster(csv)?;
+        Ok(import::preview_roster(rows, &self.repo.list_players()?))
+    }
+
+    /// `import_roster` creates the new players and updates the changed players in the roster
+    /// CSV, skipping duplicates, returning the players imported.
+    pub fn import_roster(&self, csv: &str) -> Result<Vec<Player>, Error> {
+        let players = self.repo.list_players()?;
+        let previews = import::preview_roster(import::parse_roster(csv)?, &players);
+
+        let mut created = vec![];
+        let mut updated = vec![];
+        for preview in previews {
+            let row = preview.row;
+            match preview.status {
+                RosterStatus::New => created.push(Player {
+                    attributes: row.attributes.unwrap_or_default(),
+                    position: row.position.unwrap_or_default(),
+                    ..Player::new(0, row.number, row.name)
+                }),
+                RosterStatus::Updated { player_id, .. } => {
+                    if let Some(player) = players.iter().find(|p| p.id == player_id) {
+                        updated.push(Player {
+                            name: row.name,
+                            number: row.number,
+                            attributes: row.attributes.unwrap_or_else(|| player.attributes.clone()),
+                            position: row.position.unwrap_or_else(|| player.position.clone()),
+                            ..player.clone()
+                        });
+                    }
+                }
+                RosterStatus::Duplicate(_) => {}
+            }
+        }
+
+        self.repo.import_players(created, updated)
+    }
+
     pub fn delete_player(&self, player_id: &u32) -> Result<(), Error> {
         self.repo.delete_player(player_id)
     }
@@ -49,16 +99,19 @@ impl Service {
         self.repo.list_games()
     }
 
-    pub fn create_game(&self) -> Result<Game, Error> {
-        let next = self.repo.count_games()? + 1;
+    /// `create_game` creates the next game, `fixture` being empty when it's played straight away.
+    pub fn create_game(&self, fixture: Fixture) -> Result<Game, Error> {
+        let next = self.repo.next_game_id()?;
         let players = self
             .repo
             .list_players()?
             .into_iter()
             .map(|p| p.reset_stats()) // zero game stats for new game.
             .collect();
+        let settings = self.repo.get_settings()?;
 
-        let game = Game::new(next as u32, players);
+        let mut game = Game::new(next, players, settings);
+        game.shared.set_fixture(fixture);
 
         self.repo.create_game(game.clone())?;
 
@@ -85,30 +138,105 @@ impl Service {
     }
 
     pub fn end_game(&self, game_id: &u32) -> Result<Game, Error> {
+        self.stop_game(game_id, crate::Event::EndGame, "game already ended")
+    }
+
+    /// `cancel_game` calls off a game that won't be played, it's left out of all stats.
+    pub fn cancel_game(&self, game_id: &u32) -> Result<Game, Error> {
+        self.game_event(game_id, crate::Event::Cancel, "game already cancelled")
+    }
+
+    /// `postpone_game` puts off a game until it's rescheduled.
+    pub fn postpone_game(&self, game_id: &u32) -> Result<Game, Error> {
+        self.game_event(game_id, crate::Event::Postpone, "game already postponed")
+    }
+
+    /// `reschedule_game` returns a postponed game to be played, at the new time set on its fixture.
+    pub fn reschedule_game(&self, game_id: &u32) -> Result<Game, Error> {
+        self.game_event(game_id, crate::Event::Reschedule, "game not postponed")
+    }
+
+    /// `forfeit_game` records a game that won't be played as forfeited `by` a team, giving a
+    /// result without any minutes.
+    pub fn forfeit_game(&self, game_id: &u32, by: Team) -> Result<Game, Error> {
+        self.game_event(game_id, crate::Event::Forfeit(by), "game already forfeited")
+    }
+
+    /// `abandon_game` stops a game that can't be finished, eg: weather. Everyone is subbed off and
+    /// the minutes played so far are added to the player totals.
+    pub fn abandon_game(&self, game_id: &u32) -> Result<Game, Error> {
+        self.stop_game(game_id, crate::Event::Abandon, "game already abandoned")
+    }
+
+    // stop_game ends or abandons a game, subbing everyone off and adding the minutes played to the
+    // player totals. `already` is the error when the game is already in the state.
+    fn stop_game(&self, game_id: &u32, event: crate::Event, already: &str) -> Result<Game, Error> {
         let mut game = self
             .repo
             .get_game(game_id)?
-            .on_event(crate::Event::EndGame)
+            .on_event(event)
             .map_err(|e| match e {
-                EventError::NoOp => Error::InvalidInput("game already ended".to_string()),
+                EventError::NoOp => Error::InvalidInput(already.to_string()),
                 EventError::Invalid => Error::InvalidInput("no state change".to_string()),
             })?;
 
         for p in game.shared.players.iter_mut() {
-            p.sub_off(); // game finished, everyone should be subbed off.
+            p.sub_off(); // game over, everyone should be subbed off.
         }
+        game.check_lineup();
+
+        self.repo.update_game(game.clone())?;
+        self.rebuild_player_totals()?;
+
+        Ok(game)
+    }
+
+    // game_event applies a state change with nothing else to update, `already` being the error
+    // when the game is already in the state.
+    fn game_event(&self, game_id: &u32, event: crate::Event, already: &str) -> Result<Game, Error> {
+        let game = self
+            .repo
+            .get_game(game_id)?
+            .on_event(event)
+            .map_err(|e| match e {
+                EventError::NoOp => Error::InvalidInput(already.to_string()),
+                EventError::Invalid => Error::InvalidInput("no state change".to_string()),
+            })?;
 
         self.repo.update_game(game.clone())?;
 
-        for p in &game.shared.players {
-            // N(game players) DB calls. `WHERE id IN (...)` optimization possible.
-            if let Ok(mut ep) = self.repo.get_player(&p.id) {
-                ep.add_stats(p.play_count, p.play_duration);
-                self.repo.update_player(ep)?;
+        Ok(game)
+    }
+
+    /// `rebuild_player_totals` recalculates each player's play count and duration from the
+    /// finished and abandoned games they played in. The totals on `Player` are a cache of these,
+    /// rebuilt as each game ends, so this also repairs totals after fixing a game.
+    pub fn rebuild_player_totals(&self) -> Result<Vec<Player>, Error> {
+        let games: Vec<Game> = self
+            .repo
+            .list_games()?
+            .into_iter()
+            .filter(|g| g.state.kind().has_ended())
+            .collect();
+
+        let mut players = vec![];
+        for player in self.repo.list_players()? {
+            let mut player = player.reset_stats();
+            let player_id = player.id;
+            for gp in games
+                .iter()
+                .flat_map(|g| &g.shared.players)
+                .filter(|gp| gp.id == player_id)
+            {
+                player.add_stats(gp.play_count, gp.play_duration);
             }
+
+            // N(players) DB calls, fine for a team sized roster.
+            self.repo.update_player(player.clone())?;
+            players.push(player);
         }
 
-        Ok(game)
+        Ok(players)
     }
 
     pub fn start_game_period(&self, game_id: &u32) -> Result<Game, Error> {
@@ -139,40 +267,134 @@ impl Service {
         for p in game.shared.players.iter_mut() {
             p.sub_off(); // period finished, everyone should be subbed off.
         }
+        game.check_lineup();
 
         self.repo.update_game(game.clone())?;
 
         Ok(game)
     }
 
-    pub fn sub_player_on(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+    // update_game applies `f` to the game, re-checks the lineup and saves the game.
+    fn update_game<F>(&self, game_id: &u32, f: F) -> Result<Game, Error>
+    where
+        F: FnOnce(&mut Game) -> Result<(), Error>,
+    {
         let mut game = self.repo.get_game(game_id)?;
-        let player = game
-            .shared
-            .players
-            .iter_mut()
-            .find(|p| &p.id == player_id)
-            .ok_or(Error::NotFound)?;
 
-        player.sub_on();
+        f(&mut game)?;
+        game.check_lineup();
         self.repo.update_game(game.clone())?;
 
         Ok(game)
     }
 
+    // update_game_player applies `f` to the game's copy of the player, see `update_game`.
+    fn update_game_player<F>(&self, game_id: &u32, player_id: &u32, f: F) -> Result<Game, Error>
+    where
+        F: FnOnce(&mut Player) -> Result<(), Error>,
+    {
+        self.update_game(game_id, |game| f(game_player(game, player_id)?))
+    }
+
+    pub fn sub_player_on(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if game.fouled_out(player_id) {
+                return Err(Error::InvalidInput("player has fouled out".to_string()));
+            }
+            if game.sent_off(player_id) {
+                return Err(Error::InvalidInput("player has been sent off".to_string()));
+            }
+            if game.suspended(player_id).is_some() {
+                return Err(Error::InvalidInput("player is in the sin bin".to_string()));
+            }
+            if let Some(allowed) = game.allowed_on_court()
+                && game.on_court() >= allowed
+            {
+                return Err(Error::InvalidInput(format!(
+                    "on court limit reached, {allowed} allowed"
+                )));
+            }
+
+            let substitution =
+                game.is_substitution() && !game_player(game, player_id)?.is_playing();
+            if substitution {
+                game.check_sub_rules().map_err(Error::InvalidInput)?;
+            }
+
+            let player = game_player(game, player_id)?;
+            if !player.is_available() {
+                return Err(Error::InvalidInput("player is not available".to_string()));
+            }
+
+            player.sub_on();
+            if substitution {
+                game.record_substitution(player_id);
+            }
+
+            Ok(())
+        })
+    }
+
     pub fn sub_player_off(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
-        let mut game = self.repo.get_game(game_id)?;
-        let player = game
-            .shared
-            .players
-            .iter_mut()
-            .find(|p| &p.id == player_id)
-            .ok_or(Error::NotFound)?;
+        self.update_game_player(game_id, player_id, |player| {
+            player.sub_off();
+            Ok(())
+        })
+    }
 
-        player.sub_off();
-        self.repo.update_game(game.clone())?;
+    pub fn mark_player_late(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game_player(game_id, player_id, |player| {
+            if player.is_playing() {
+                return Err(Error::InvalidInput("player is on court".to_string()));
+            }
 
-        Ok(game)
+            player.mark_late();
+            Ok(())
+        })
+    }
+
+    pub fn player_arrived(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game_player(game_id, player_id, |player| {
+            if player.is_available() {
+                return Err(Error::InvalidInput("player is already here".to_string()));
+            }
+
+            player.arrive();
+            Ok(())
+        })
+    }
+
+    pub fn player_departed(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game_player(game_id, player_id, |player| {
+            player.depart(); // subs off if on court.
+            Ok(())
+        })
+    }
+
+    /// `update_fixture` sets who, when and where the game is played.
+    pub fn update_fixture(&self, game_id: &u32, fixture: Fixture) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            game.shared.set_fixture(fixture);
+            Ok(())
+        })
+    }
+
+    /// `open_game` readies a scheduled game on match day, copying in the current players and
+    /// settings as they may have changed since the fixture was entered.
+    pub fn open_game(&self, game_id: &u32) -> Result<Game, Error> {
+        let players = self.repo.list_players()?;
+        let settings = self.repo.get_settings()?;
+
+        self.update_game(game_id, |game| {
+            if game.state.kind() != GameState::NotStarted {
+                return Err(Error::InvalidInput("game already started".to_string()));
+            }
+
+            game.refresh_roster(&players);
+            game.shared.settings = settings;
+            game.check_lineup();
+            Ok(())
+        })
     }
 
     pub fn upsert_mvp(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
@@ -184,8 +406,918 @@ impl Service {
             .ok_or(Error::NotFound)?;
 
         game.shared.mvp = Some(*player_id);
+        // confirming the MVP ends any voting.
+        game.close_voting();
         self.repo.update_game(game.clone())?;
 
         Ok(game)
     }
+
+    /// `upsert_award` sets the winner of one of the game's award categories, `None` clearing it.
+    pub fn upsert_award(
+        &self,
+        game_id: &u32,
+        category: &str,
+        player_id: Option<u32>,
+    ) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game
+                .shared
+                .settings
+                .award_categories
+                .iter()
+                .any(|c| c == category)
+            {
+                return Err(Error::InvalidInput("unknown award category".to_string()));
+            }
+            if let Some(player_id) = &player_id {
+                game_player(game, player_id)?;
+            }
+
+            game.set_award(category, player_id);
+            Ok(())
+        })
+    }
+
+    /// `open_voting` opens player of the match voting for a game that has started.
+    pub fn open_voting(&self, game_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.state.kind().is_played() {
+                return Err(Error::InvalidInput("game has not been played".to_string()));
+            }
+
+            game.open_voting();
+            Ok(())
+        })
+    }
+
+    pub fn close_voting(&self, game_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            game.close_voting();
+            Ok(())
+        })
+    }
+
+    /// `get_voting_game` finds the game a voting link is for.
+    pub fn get_voting_game(&self, token: &str) -> Result<Game, Error> {
+        self.repo
+            .list_games()?
+            .into_iter()
+            .find(|g| g.shared.voting.as_ref().is_some_and(|v| v.token == token))
+            .ok_or(Error::NotFound)
+    }
+
+    /// `cast_vote` records a voter's vote, each `device_id` having a single vote. Voters don't log
+    /// in, so only the vote is stored and the game itself is never written.
+    pub fn cast_vote(&self, token: &str, device_id: &str, player_id: &u32) -> Result<Game, Error> {
+        let game = self.get_voting_game(token)?;
+        if !game.shared.players.iter().any(|p| &p.id == player_id) {
+            return Err(Error::NotFound);
+        }
+
+        let vote = game
+            .vote(device_id, player_id)
+            .ok_or_else(|| Error::InvalidInput("voting is closed".to_string()))?;
+        self.repo.cast_vote(&game.id, vote)?;
+
+        self.repo.get_game(&game.id)
+    }
+
+    /// `record_incident` logs an injury or other incident, subbing the player off.
+    pub fn record_incident(
+        &self,
+        game_id: &u32,
+        player_id: &u32,
+        description: &str,
+    ) -> Result<Game, Error> {
+        let description = description.trim();
+        if description.is_empty() {
+            return Err(Error::InvalidInput("description is required".to_string()));
+        }
+
+        self.update_game(game_id, |game| {
+            game_player(game, player_id)?;
+
+            if !game.record_incident(player_id, description) {
+                return Err(Error::InvalidInput("game has not started".to_string()));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_incident(&self, game_id: &u32, index: usize) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_incident(index) {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// `add_note` adds a note about the game, or a player in it, `tags` being comma separated.
+    pub fn add_note(
+        &self,
+        game_id: &u32,
+        player_id: Option<u32>,
+        text: &str,
+        tags: &str,
+    ) -> Result<Game, Error> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Err(Error::InvalidInput("note is required".to_string()));
+        }
+
+        let mut note_tags: Vec<String> = vec![];
+        for tag in tags.split(',').map(|t| t.trim().to_lowercase()) {
+            if !tag.is_empty() && !note_tags.contains(&tag) {
+                note_tags.push(tag);
+            }
+        }
+
+        self.update_game(game_id, |game| {
+            if let Some(player_id) = &player_id {
+                game_player(game, player_id)?;
+            }
+
+            game.add_note(player_id, text, note_tags);
+            Ok(())
+        })
+    }
+
+    pub fn remove_note(&self, game_id: &u32, index: usize) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_note(index) {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn record_foul(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            game_player(game, player_id)?;
+
+            if !game.record_foul(player_id) {
+                return Err(Error::InvalidInput("no period in progress".to_string()));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_foul(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_foul(player_id) {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn record_card(
+        &self,
+        game_id: &u32,
+        player_id: &u32,
+        kind: CardKind,
+    ) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            game_player(game, player_id)?;
+
+            if !game.record_card(player_id, kind) {
+                return Err(Error::InvalidInput("game not in progress".to_string()));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_card(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_card(player_id) {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// `record_score` adds points scored by the player for our team.
+    pub fn record_score(&self, game_id: &u32, player_id: &u32, points: u32) -> Result<Game, Error> {
+        self.record_team_score(game_id, Team::Us, Some(*player_id), points)
+    }
+
+    pub fn record_opponent_score(&self, game_id: &u32, points: u32) -> Result<Game, Error> {
+        self.record_team_score(game_id, Team::Them, None, points)
+    }
+
+    fn record_team_score(
+        &self,
+        game_id: &u32,
+        team: Team,
+        player_id: Option<u32>,
+        points: u32,
+    ) -> Result<Game, Error> {
+        if points == 0 {
+            return Err(Error::InvalidInput(
+                "points must be more than 0".to_string(),
+            ));
+        }
+
+        self.update_game(game_id, |game| {
+            if let Some(player_id) = &player_id {
+                game_player(game, player_id)?;
+            }
+
+            if !game.record_score(team, player_id, points) {
+                return Err(Error::InvalidInput("no period in progress".to_string()));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_score(&self, game_id: &u32, player_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_score(Team::Us, Some(*player_id)) {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_opponent_score(&self, game_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_score(Team::Them, None) {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn record_shot(
+        &self,
+        game_id: &u32,
+        player_id: &u32,
+        (x, y): (u32, u32),
+        made: bool,
+    ) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            game_player(game, player_id)?;
+
+            if !game.record_shot(player_id, x, y, made) {
+                return Err(Error::InvalidInput("no period in progress".to_string()));
+            }
+
+            Ok(())
+        })
+    }
+
+    pub fn remove_shot(&self, game_id: &u32) -> Result<Game, Error> {
+        self.update_game(game_id, |game| {
+            if !game.remove_shot() {
+                return Err(Error::NotFound);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// `season_shots` returns every shot taken on the `surface` in the `season`, optionally only the
+    /// player's. Shots on other surfaces are located in different units.
+    pub fn season_shots(
+        &self,
+        season: &str,
+        surface: Surface,
+        player_id: Option<u32>,
+    ) -> Result<Vec<Shot>, Error> {
+        let games = self.repo.list_games()?;
+        let shots = stats::in_season(&games, season)
+            .filter(|g| g.shared.settings.surface == surface)
+            .flat_map(|g| g.shared.shots.clone())
+            .filter(|s| player_id.is_none_or(|id| s.player_id == id))
+            .collect();
+
+        Ok(shots)
+    }
+
+    pub fn shooting_totals(&self, season: &str) -> Result<Vec<ShootingTotals>, Error> {
+        let games = self.repo.list_games()?;
+        Ok(stats::shooting_totals(stats::in_season(&games, season)))
+    }
+
+    /// `list_seasons` returns the seasons games have been played in, plus the current season.
+    pub fn list_seasons(&self) -> Result<Vec<String>, Error> {
+        let mut seasons = stats::seasons(&self.repo.list_games()?);
+        let current = self.repo.get_settings()?.season;
+        if !seasons.contains(&current) {
+            seasons.insert(0, current);
+        }
+
+        Ok(seasons)
+    }
+
+    pub fn season_finished_games(&self, season: &str) -> Result<Vec<Game>, Error> {
+        Ok(stats::season_finished_games(
+            &self.repo.list_games()?,
+            season,
+        ))
+    }
+
+    pub fn season_participation(&self, season: &str) -> Result<Vec<SeasonParticipation>, Error> {
+        Ok(stats::season_participation(
+            &self.repo.list_games()?,
+            season,
+        ))
+    }
+
+    pub fn discipline_totals(&self, season: &str) -> Result<Vec<DisciplineTotals>, Error> {
+        Ok(stats::discipline_totals(&self.repo.list_games()?, season))
+    }
+
+    pub fn plus_minus_totals(&self, season: &str) -> Result<Vec<PlusMinusTotals>, Error> {
+        Ok(stats::plus_minus_totals(&self.repo.list_games()?, season))
+    }
+
+    pub fn season_lineups(&self, season: &str) -> Result<Vec<Lineup>, Error> {
+        Ok(stats::season_lineups(&self.repo.list_games()?, season))
+    }
+
+    pub fn award_tallies(&self, season: &str) -> Result<(Vec<String>, Vec<AwardTally>), Error> {
+        Ok(stats::award_tallies(&self.repo.list_games()?, season))
+    }
+
+    pub fn incidents(&self, season: &str) -> Result<Vec<IncidentEntry>, Error> {
+        Ok(stats::incidents(&self.repo.list_games()?, season))
+    }
+
+    pub fn player_notes(
+        &self,
+        season: &str,
+        player_id: &u32,
+        tag: Option<&str>,
+    ) -> Result<Vec<NoteEntry>, Error> {
+        Ok(stats::player_notes(
+            &self.repo.list_games()?,
+            season,
+            player_id,
+            tag,
+        ))
+    }
+
+    pub fn player_season(&self, season: &str, player_id: &u32) -> Result<PlayerSeason, Error> {
+        Ok(stats::player_season(
+            &self.repo.list_games()?,
+            season,
+            player_id,
+        ))
+    }
+
+    pub fn leaderboard(
+        &self,
+        filter: &LeaderboardFilter,
+        sort: LeaderboardSort,
+    ) -> Result<Vec<LeaderboardRow>, Error> {
+        Ok(stats::leaderboard(&self.repo.list_games()?, filter, sort))
+    }
+
+    pub fn season_minutes(&self, filter: &LeaderboardFilter) -> Result<SeasonMinutes, Error> {
+        Ok(stats::season_minutes(&self.repo.list_games()?, filter))
+    }
+
+    pub fn list_opponents(&self, season: &str) -> Result<Vec<String>, Error> {
+        Ok(stats::opponents(&self.repo.list_games()?, season))
+    }
+
+    pub fn export_players_csv(&self) -> Result<String, Error> {
+        Ok(export::players_csv(&self.repo.list_players()?))
+    }
+
+    pub fn export_games_csv(&self) -> Result<String, Error> {
+        Ok(export::games_csv(&self.repo.list_games()?))
+    }
+
+    pub fn export_game_minutes_csv(&self, game_id: &u32) -> Result<String, Error> {
+        Ok(export::game_minutes_csv(&self.repo.get_game(game_id)?))
+    }
+
+    /// `reset_calendar_token` changes the calendar feed secret, so previously shared links stop
+    /// working.
+    pub fn reset_calendar_token(&self) -> Result<String, Error> {
+        let token = uuid::Uuid::new_v4().simple().to_string();
+        self.repo.update_calendar_token(&token)?;
+
+        Ok(token)
+    }
+
+    /// `disable_calendar` clears the calendar feed secret, turning the feed off.
+    pub fn disable_calendar(&self) -> Result<String, Error> {
+        self.repo.update_calendar_token("")?;
+
+        Ok(String::new())
+    }
+
+    /// `calendar_token` returns the calendar feed secret, empty when the feed is disabled.
+    pub fn calendar_token(&self) -> Result<String, Error> {
+        self.repo.get_calendar_token()
+    }
+
+    /// `calendar_games` returns the games for the calendar feed, `Error::NotFound` unless the
+    /// `token` matches the enabled feed's.
+    pub fn calendar_games(&self, token: &str) -> Result<Vec<Game>, Error> {
+        let calendar_token = self.repo.get_calendar_token()?;
+        if calendar_token.is_empty() || calendar_token != token {
+            return Err(Error::NotFound);
+        }
+
+        self.repo.list_games()
+    }
+
+    pub fn export_dump(&self) -> Result<Dump, Error> {
+        Ok(Dump {
+            version: DUMP_VERSION,
+            exported_at: Utc::now(),
+            settings: self.repo.get_settings()?,
+            players: self.repo.list_players()?,
+            games: self.repo.list_games()?,
+        })
+    }
+
+    /// `import_dump` loads the `dump`, replacing all players and games or merging them in with
+    /// new ids, see `Dump::merge`. Player totals are rebuilt from the games afterwards.
+    pub fn import_dump(&self, dump: Dump, mode: ImportMode) -> Result<ImportSummary, Error> {
+        let summary = match mode {
+            ImportMode::Replace => {
+                let summary = ImportSummary {
+                    players: dump.players.len(),
+                    games: dump.games.len(),
+                    matched: 0,
+                };
+                self.repo
+                    .restore(Some(dump.settings), dump.players, dump.games, true)?;
+                summary
+            }
+            ImportMode::Merge => {
+                let existing = self.repo.list_players()?;
+                // deleted players are still in the rosters of their games, don't reuse their ids.
+                let games = self.repo.list_games()?;
+                let next_player_id = existing
+                    .iter()
+                    .chain(games.iter().flat_map(|g| &g.shared.players))
+                    .map(|p| p.id)
+                    .max()
+                    .map_or(1, |id| id + 1);
+                let merge = dump.merge(&existing, next_player_id, self.repo.next_game_id()?);
+                self.repo.restore(None, merge.players, merge.games, false)?;
+                merge.summary
+            }
+        };
+        self.rebuild_player_totals()?;
+
+        Ok(summary)
+    }
+
+    pub fn get_settings(&self) -> Result<Settings, Error> {
+        self.repo.get_settings()
+    }
+
+    pub fn update_settings(&self, settings: Settings) -> Result<Settings, Error> {
+        if settings.season.is_empty() {
+            return Err(Error::InvalidInput("season cannot be empty".to_string()));
+        }
+
+        self.repo.update_settings(settings.clone())?;
+
+        Ok(settings)
+    }
+
+    pub fn add_lineup_rule(&self, rule: LineupRule) -> Result<Settings, Error> {
+        let mut settings = self.repo.get_settings()?;
+        settings.lineup_rules.push(rule);
+        self.repo.update_settings(settings.clone())?;
+
+        Ok(settings)
+    }
+
+    pub fn delete_lineup_rule(&self, index: usize) -> Result<Settings, Error> {
+        let mut settings = self.repo.get_settings()?;
+        if index >= settings.lineup_rules.len() {
+            return Err(Error::NotFound);
+        }
+
+        settings.lineup_rules.remove(index);
+        self.repo.update_settings(settings.clone())?;
+
+        Ok(settings)
+    }
+
+    pub fn add_award_category(&self, category: &str) -> Result<Settings, Error> {
+        let category = category.trim();
+        if category.is_empty() {
+            return Err(Error::InvalidInput(
+                "award category is required".to_string(),
+            ));
+        }
+        if category.eq_ignore_ascii_case(stats::MVP) {
+            return Err(Error::InvalidInput("MVP is always awarded".to_string()));
+        }
+
+        let mut settings = self.repo.get_settings()?;
+        if settings
+            .award_categories
+            .iter()
+            .any(|c| c.eq_ignore_ascii_case(category))
+        {
+            return Err(Error::Conflict);
+        }
+
+        settings.award_categories.push(category.to_string());
+        self.repo.update_settings(settings.clone())?;
+
+        Ok(settings)
+    }
+
+    pub fn delete_award_category(&self, index: usize) -> Result<Settings, Error> {
+        let mut settings = self.repo.get_settings()?;
+        if index >= settings.award_categories.len() {
+            return Err(Error::NotFound);
+        }
+
+        settings.award_categories.remove(index);
+        self.repo.update_settings(settings.clone())?;
+
+        Ok(settings)
+    }
+}
+
+// game_player finds the game's copy of the player.
+fn game_player<'a>(game: &'a mut Game, player_id: &u32) -> Result<&'a mut Player, Error> {
+    game.shared
+        .players
+        .iter_mut()
+        .find(|p| &p.id == player_id)
+        .ok_or(Error::NotFound)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Attribute;
+    use crate::settings::SubRule;
+    use crate::{InMemoryRepo, SqliteRepo};
+    use chrono::TimeDelta;
+
+    fn repos() -> Vec<Arc<dyn Repo>> {
+        vec![
+            Arc::new(InMemoryRepo::new()),
+            Arc::new(SqliteRepo::new(None).unwrap()),
+        ]
+    }
+
+    #[test]
+    fn test_vote_survives_game_update() {
+        for repo in repos() {
+            let svc = Service::new(repo.clone());
+            let player = svc.create_player(1, "foo".to_string()).unwrap();
+            let game = svc.create_game(Fixture::default()).unwrap();
+            svc.start_game(&game.id).unwrap();
+            let token = svc
+                .open_voting(&game.id)
+                .unwrap()
+                .shared
+                .voting
+                .unwrap()
+                .token;
+
+            // the coach's copy of the game from before the vote is saved after it.
+            let stale = svc.get_game(&game.id).unwrap();
+            svc.cast_vote(&token, "device", &player.id).unwrap();
+            repo.update_game(stale).unwrap();
+
+            let game = svc.get_game(&game.id).unwrap();
+            assert_eq!(game.vote_tally(), vec![(player.id, 1)]);
+        }
+    }
+
+    #[test]
+    fn test_vote_replaces_device_vote() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            let foo = svc.create_player(1, "foo".to_string()).unwrap();
+            let bar = svc.create_player(2, "bar".to_string()).unwrap();
+            let game = svc.create_game(Fixture::default()).unwrap();
+            svc.start_game(&game.id).unwrap();
+            let token = svc
+                .open_voting(&game.id)
+                .unwrap()
+                .shared
+                .voting
+                .unwrap()
+                .token;
+
+            svc.cast_vote(&token, "device", &foo.id).unwrap();
+            let game = svc.cast_vote(&token, "device", &bar.id).unwrap();
+            assert_eq!(game.vote_tally(), vec![(bar.id, 1)]);
+
+            svc.close_voting(&game.id).unwrap();
+            assert!(matches!(
+                svc.cast_vote(&token, "other", &foo.id),
+                Err(Error::InvalidInput(_))
+            ));
+        }
+    }
+
+    // dump_with_scores returns a dump of a game where "foo" and "bar" both scored.
+    fn dump_with_scores() -> Dump {
+        let svc = Service::new(Arc::new(InMemoryRepo::new()));
+        let foo = svc.create_player(1, "foo".to_string()).unwrap();
+        let bar = svc.create_player(2, "bar".to_string()).unwrap();
+        let game = svc.create_game(Fixture::default()).unwrap();
+        svc.start_game(&game.id).unwrap();
+        svc.record_score(&game.id, &foo.id, 2).unwrap();
+        svc.record_score(&game.id, &bar.id, 3).unwrap();
+        svc.upsert_mvp(&game.id, &foo.id).unwrap();
+
+        svc.export_dump().unwrap()
+    }
+
+    // scorer returns the id of the player who scored `points` in the game.
+    fn scorer(game: &Game, points: u32) -> Option<u32> {
+        game.shared
+            .scores
+            .iter()
+            .find(|s| s.points == points)
+            .and_then(|s| s.player_id)
+    }
+
+    #[test]
+    fn test_import_merge_remaps_ids() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            // "baz" has the id "foo" has in the dump, "BAR" is "bar" on this machine.
+            let baz = svc.create_player(7, "baz".to_string()).unwrap();
+            let bar = svc.create_player(8, "BAR".to_string()).unwrap();
+            let existing = svc.create_game(Fixture::default()).unwrap();
+
+            let summary = svc
+                .import_dump(dump_with_scores(), ImportMode::Merge)
+                .unwrap();
+            assert_eq!(
+                summary,
+                ImportSummary {
+                    players: 1,
+                    games: 1,
+                    matched: 1,
+                }
+            );
+
+            let players = svc.list_players().unwrap();
+            assert_eq!(players.len(), 3);
+            let foo = players.iter().find(|p| p.name == "foo").unwrap();
+            assert_ne!(foo.id, baz.id);
+            assert_ne!(foo.id, bar.id);
+            assert_eq!(svc.get_player(&baz.id).unwrap().name, "baz");
+
+            let games = svc.list_games().unwrap();
+            assert_eq!(games.len(), 2);
+            let game = games.iter().find(|g| g.id != existing.id).unwrap();
+            assert_eq!(scorer(game, 2), Some(foo.id));
+            assert_eq!(scorer(game, 3), Some(bar.id));
+            assert_eq!(game.shared.mvp, Some(foo.id));
+            assert_eq!(svc.get_game(&existing.id).unwrap().shared.scores.len(), 0);
+
+            // new ids follow the highest, not the count.
+            let next = svc.create_game(Fixture::default()).unwrap();
+            assert!(next.id > game.id);
+            let next = svc.create_player(9, "qux".to_string()).unwrap();
+            assert!(next.id > foo.id);
+        }
+    }
+
+    #[test]
+    fn test_import_merge_skips_deleted_player_ids() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            svc.create_player(7, "baz".to_string()).unwrap();
+            let deleted = svc.create_player(8, "qux".to_string()).unwrap();
+            let game = svc.create_game(Fixture::default()).unwrap();
+            svc.delete_player(&deleted.id).unwrap();
+
+            svc.import_dump(dump_with_scores(), ImportMode::Merge)
+                .unwrap();
+
+            // the deleted player's game history isn't given to an imported player.
+            let players = svc.list_players().unwrap();
+            assert_eq!(players.len(), 3);
+            assert!(players.iter().all(|p| p.id != deleted.id));
+            let game = svc.get_game(&game.id).unwrap();
+            assert!(game.shared.players.iter().any(|p| p.id == deleted.id));
+        }
+    }
+
+    #[test]
+    fn test_import_replace_keeps_ids() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            svc.create_player(7, "baz".to_string()).unwrap();
+            svc.create_game(Fixture::default()).unwrap();
+            svc.create_game(Fixture::default()).unwrap();
+            let dump = dump_with_scores();
+            let ids: Vec<u32> = dump.players.iter().map(|p| p.id).collect();
+            let foo = dump.players.iter().find(|p| p.name == "foo").unwrap().id;
+
+            let summary = svc.import_dump(dump, ImportMode::Replace).unwrap();
+            assert_eq!(
+                summary,
+                ImportSummary {
+                    players: 2,
+                    games: 1,
+                    matched: 0,
+                }
+            );
+
+            let players = svc.list_players().unwrap();
+            assert!(players.iter().all(|p| ids.contains(&p.id)));
+            assert!(!players.iter().any(|p| p.name == "baz"));
+            let games = svc.list_games().unwrap();
+            assert_eq!(games.len(), 1);
+            assert_eq!(scorer(&games[0], 2), Some(foo));
+        }
+    }
+
+    #[test]
+    fn test_create_game_after_delete() {
+        for repo in repos() {
+            let svc = Service::new(repo.clone());
+            let first = svc.create_game(Fixture::default()).unwrap();
+            let second = svc.create_game(Fixture::default()).unwrap();
+            repo.delete_game(&first.id).unwrap();
+
+            let third = svc.create_game(Fixture::default()).unwrap();
+            assert!(third.id > second.id);
+        }
+    }
+
+    #[test]
+    fn test_abandon_keeps_minutes_played() {
+        for repo in repos() {
+            let svc = Service::new(repo.clone());
+            let on = svc.create_player(1, "on".to_string()).unwrap();
+            let bench = svc.create_player(2, "bench".to_string()).unwrap();
+            let game = svc.create_game(Fixture::default()).unwrap();
+            svc.start_game(&game.id).unwrap();
+            svc.sub_player_on(&game.id, &on.id).unwrap();
+
+            // on court for 10 minutes before the game was stopped.
+            let mut playing = svc.get_game(&game.id).unwrap();
+            for p in playing.shared.players.iter_mut().filter(|p| p.id == on.id) {
+                p.play_start_time = Some(Utc::now() - TimeDelta::minutes(10));
+            }
+            repo.update_game(playing).unwrap();
+            svc.abandon_game(&game.id).unwrap();
+
+            let on = svc.get_player(&on.id).unwrap();
+            assert_eq!(on.play_count, 1);
+            assert!(on.play_duration >= TimeDelta::minutes(10));
+            let bench = svc.get_player(&bench.id).unwrap();
+            assert_eq!(bench.play_count, 0);
+            assert_eq!(bench.play_duration, TimeDelta::zero());
+        }
+    }
+
+    #[test]
+    fn test_import_players_all_or_nothing() {
+        for repo in repos() {
+            let existing = repo.create_player(1, "foo".to_string()).unwrap();
+            let created = vec![Player::new(0, 2, "bar".to_string())];
+            let missing = Player::new(existing.id + 100, 3, "baz".to_string());
+
+            assert!(matches!(
+                repo.import_players(created.clone(), vec![missing]),
+                Err(Error::NotFound)
+            ));
+            assert_eq!(repo.list_players().unwrap().len(), 1);
+
+            let renamed = Player {
+                name: "qux".to_string(),
+                ..existing.clone()
+            };
+            let players = repo.import_players(created, vec![renamed]).unwrap();
+            assert_eq!(players.len(), 2);
+            assert_ne!(players[0].id, existing.id);
+            assert_eq!(repo.get_player(&existing.id).unwrap().name, "qux");
+        }
+    }
+
+    #[test]
+    fn test_sub_player_on_limits() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            svc.update_settings(Settings {
+                on_court_limit: 1,
+                sub_rule: SubRule::MaxSubs,
+                sub_limit: 1,
+                ..svc.get_settings().unwrap()
+            })
+            .unwrap();
+            let ids: Vec<u32> = (1..=3)
+                .map(|n| svc.create_player(n, n.to_string()).unwrap().id)
+                .collect();
+            let game = svc.create_game(Fixture::default()).unwrap();
+            svc.start_game(&game.id).unwrap();
+
+            // the starter isn't a substitution, the on court limit still applies.
+            svc.sub_player_on(&game.id, &ids[0]).unwrap();
+            assert!(matches!(
+                svc.sub_player_on(&game.id, &ids[1]),
+                Err(Error::InvalidInput(_))
+            ));
+
+            // the one substitution allowed.
+            svc.sub_player_off(&game.id, &ids[0]).unwrap();
+            let game = svc.sub_player_on(&game.id, &ids[1]).unwrap();
+            assert_eq!(game.subs_made(), 1);
+
+            svc.sub_player_off(&game.id, &ids[1]).unwrap();
+            assert!(matches!(
+                svc.sub_player_on(&game.id, &ids[2]),
+                Err(Error::InvalidInput(_))
+            ));
+            assert_eq!(svc.get_game(&game.id).unwrap().subs_made(), 1);
+        }
+    }
+
+    #[test]
+    fn test_import_roster_keeps_missing_columns() {
+        for repo in repos() {
+            let svc = Service::new(repo.clone());
+            let player = Player {
+                position: "guard".to_string(),
+                attributes: vec![Attribute::Tall],
+                ..svc.create_player(7, "Jo".to_string()).unwrap()
+            };
+            repo.update_player(player.clone()).unwrap();
+
+            let players = svc.import_roster("name,number\njo,9\n").unwrap();
+            assert_eq!(players.len(), 1);
+            let updated = svc.get_player(&player.id).unwrap();
+            assert_eq!((updated.name.as_str(), updated.number), ("jo", 9));
+            assert_eq!(updated.position, "guard");
+            assert_eq!(updated.attributes, vec![Attribute::Tall]);
+        }
+    }
+
+    #[test]
+    fn test_player_arrived_only_when_away() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            let player = svc.create_player(1, "foo".to_string()).unwrap();
+            let game = svc.create_game(Fixture::default()).unwrap();
+            svc.start_game(&game.id).unwrap();
+
+            assert!(matches!(
+                svc.player_arrived(&game.id, &player.id),
+                Err(Error::InvalidInput(_))
+            ));
+
+            svc.mark_player_late(&game.id, &player.id).unwrap();
+            let game = svc.player_arrived(&game.id, &player.id).unwrap();
+            let arrived_at = game.shared.players[0].arrived_at;
+            assert!(arrived_at.is_some());
+            assert!(matches!(
+                svc.player_arrived(&game.id, &player.id),
+                Err(Error::InvalidInput(_))
+            ));
+            let game = svc.get_game(&game.id).unwrap();
+            assert_eq!(game.shared.players[0].arrived_at, arrived_at);
+
+            svc.player_departed(&game.id, &player.id).unwrap();
+            let game = svc.player_arrived(&game.id, &player.id).unwrap();
+            assert_eq!(game.shared.players[0].departed_at, None);
+        }
+    }
+
+    #[test]
+    fn test_saving_game_counts_revision() {
+        for repo in repos() {
+            let svc = Service::new(repo);
+            let game = svc.create_game(Fixture::default()).unwrap();
+            let created = svc.get_game(&game.id).unwrap();
+            assert_eq!(created.shared.revision, 1);
+
+            let fixture = Fixture {
+                opponent: "Tigers".to_string(),
+                ..Fixture::default()
+            };
+            svc.update_fixture(&game.id, fixture).unwrap();
+            let updated = svc.get_game(&game.id).unwrap();
+            assert_eq!(updated.shared.revision, 2);
+            assert!(updated.shared.updated_at >= created.shared.updated_at);
+        }
+    }
 }